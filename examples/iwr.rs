@@ -0,0 +1,260 @@
+//! A small subset of `iw` built on neli-wifi.
+//!
+//! ```text
+//! cargo run --example iwr --features iwr -- dev
+//! cargo run --example iwr --features iwr -- phy
+//! cargo run --example iwr --features iwr -- wlan0 scan
+//! cargo run --example iwr --features iwr -- wlan0 scan dump
+//! cargo run --example iwr --features iwr -- wlan0 link
+//! cargo run --example iwr --features iwr -- wlan0 survey dump
+//! cargo run --example iwr --features iwr -- event
+//! ```
+
+use neli_wifi::{Bss, Interface, Nl80211NotAvailable, ScanParams, Signal, WifiClient};
+use std::env;
+use std::error::Error;
+use std::process;
+
+const USAGE: &str = "usage: iwr dev | phy | event | <dev> (scan [dump] | link | survey dump)";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let mut client = match WifiClient::connect() {
+        Ok(client) => client,
+        Err(err) if Nl80211NotAvailable::matches(&err) => {
+            eprintln!("nl80211 not found, is cfg80211 loaded?");
+            process::exit(1);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    match args.as_slice() {
+        ["dev"] => dev(&mut client),
+        ["phy"] => phy(&mut client),
+        ["event"] => event(&mut client),
+        [name, command @ ..] => {
+            let interface = find_interface(&mut client, name)?;
+            let index = interface.index.ok_or("the interface has no index")?;
+            match command {
+                ["scan"] => {
+                    client.scan(index, &ScanParams::default())?;
+                    print_bss(&client.get_bss_info(index)?);
+                    Ok(())
+                }
+                ["scan", "dump"] => {
+                    print_bss(&client.get_bss_info(index)?);
+                    Ok(())
+                }
+                ["link"] => link(&mut client, index),
+                ["survey", "dump"] => survey(&mut client, index),
+                _ => usage(),
+            }
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> Result<(), Box<dyn Error>> {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn find_interface(client: &mut WifiClient, name: &str) -> Result<Interface, Box<dyn Error>> {
+    client
+        .get_interfaces_info()?
+        .into_iter()
+        .find(|interface| interface.name.as_deref().map(trim_nul) == Some(name.as_bytes()))
+        .ok_or_else(|| format!("no wireless interface named {}", name).into())
+}
+
+fn dev(client: &mut WifiClient) -> Result<(), Box<dyn Error>> {
+    let wiphys = client.get_wiphys()?;
+    for interface in client.get_interfaces_info()? {
+        let phy = interface
+            .find_wiphy(&wiphys)
+            .and_then(|wiphy| wiphy.name.clone())
+            .unwrap_or_else(|| format!("phy{}", interface.phy.unwrap_or_default()));
+        println!("{}", phy);
+        println!("\tInterface {}", text(interface.name.as_deref()));
+        if let Some(index) = interface.index {
+            println!("\t\tifindex {}", index);
+        }
+        if let Some(mac) = &interface.mac {
+            println!("\t\taddr {}", format_mac(mac));
+        }
+        if let Some(ssid) = &interface.ssid {
+            println!("\t\tssid {}", text(Some(ssid)));
+        }
+        if let Some(frequency) = interface.frequency {
+            print!("\t\tchannel {} MHz", frequency);
+            if let Some(width) = interface.channel_width {
+                print!(", width: {:?}", width);
+            }
+            if let Some(center) = interface.center_frequency1 {
+                print!(", center1: {} MHz", center);
+            }
+            println!();
+        }
+        if let Some(power) = interface.tx_power() {
+            println!("\t\ttxpower {}", power);
+        }
+        if let Some(state) = interface.link_state {
+            println!(
+                "\t\tstate {}{}",
+                if state.up { "UP" } else { "DOWN" },
+                if state.carrier { ", LOWER_UP" } else { "" }
+            );
+        }
+    }
+    Ok(())
+}
+
+fn phy(client: &mut WifiClient) -> Result<(), Box<dyn Error>> {
+    for wiphy in client.get_wiphys()? {
+        println!("Wiphy {}", wiphy.name.as_deref().unwrap_or("<unknown>"));
+        if let Some(max) = wiphy.max_scan_ssids {
+            println!("\tmax # scan SSIDs: {}", max);
+        }
+        if let Some(threshold) = wiphy.frag_threshold {
+            println!("\tFragmentation threshold: {}", threshold);
+        }
+        if let Some(threshold) = wiphy.rts_threshold {
+            println!("\tRTS threshold: {}", threshold);
+        }
+        if let (Some(short), Some(long)) = (wiphy.retry_short, wiphy.retry_long) {
+            println!("\tRetry short limit: {}, long limit: {}", short, long);
+        }
+        if let Some(coverage_class) = wiphy.coverage_class {
+            println!("\tCoverage class: {}", coverage_class);
+        }
+        println!("\tSupported interface modes:");
+        for iftype in &wiphy.supported_iftypes {
+            println!("\t\t * {:?}", iftype);
+        }
+        println!("\tFrequencies:");
+        for channel in &wiphy.channels {
+            print!("\t\t* {} MHz", channel.frequency);
+            if let Some(power) = channel.max_tx_power {
+                print!(" ({})", power);
+            }
+            if channel.disabled {
+                print!(" (disabled)");
+            } else if channel.radar {
+                print!(" (radar detection, {:?})", channel.dfs_state);
+            }
+            println!();
+        }
+        if !wiphy.wowlan_triggers.is_empty() {
+            println!("\tWoWLAN support:");
+            for trigger in &wiphy.wowlan_triggers {
+                println!("\t\t * {:?}", trigger);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_bss(bss_list: &[Bss]) {
+    for bss in bss_list {
+        println!(
+            "BSS {}",
+            bss.bssid.as_deref().map(format_mac).unwrap_or_default()
+        );
+        if let Some(frequency) = bss.frequency {
+            println!("\tfreq: {}", frequency);
+        }
+        if let Some(interval) = bss.beacon_interval {
+            println!("\tbeacon interval: {} TUs", interval);
+        }
+        match bss.signal() {
+            Some(Signal::Dbm(signal)) => println!("\tsignal: {}", signal),
+            Some(Signal::Unspec(quality)) => println!("\tsignal: {}/100", quality),
+            None => (),
+        }
+        if let Some(seen) = bss.seen_ms_ago {
+            println!("\tlast seen: {} ms ago", seen);
+        }
+        println!("\tSSID: {}", text(bss.ssid()));
+    }
+}
+
+fn link(client: &mut WifiClient, index: i32) -> Result<(), Box<dyn Error>> {
+    let station = client.get_station_info(index)?;
+    let bssid = match &station.bssid {
+        Some(bssid) => bssid,
+        None => {
+            println!("Not connected.");
+            return Ok(());
+        }
+    };
+    println!("Connected to {}", format_mac(bssid));
+    if let Some(time) = station.connected_time {
+        println!("\tconnected time: {} seconds", time);
+    }
+    if let (Some(rx_bytes), Some(rx_packets)) = (station.rx_bytes, station.rx_packets) {
+        println!("\tRX: {} bytes ({} packets)", rx_bytes, rx_packets);
+    }
+    if let (Some(tx_bytes), Some(tx_packets)) = (station.tx_bytes, station.tx_packets) {
+        println!("\tTX: {} bytes ({} packets)", tx_bytes, tx_packets);
+    }
+    if let Some(signal) = station.signal {
+        println!("\tsignal: {} dBm", signal);
+    }
+    if let Some(rate) = &station.rx_rate {
+        println!("\trx bitrate: {}", rate);
+    }
+    if let Some(rate) = &station.tx_rate {
+        println!("\ttx bitrate: {}", rate);
+    }
+    Ok(())
+}
+
+fn survey(client: &mut WifiClient, index: i32) -> Result<(), Box<dyn Error>> {
+    for survey in client.get_survey(index)? {
+        println!("Survey data from {}", index);
+        if let Some(frequency) = survey.frequency {
+            let in_use = if survey.in_use { " [in use]" } else { "" };
+            println!("\tfrequency:\t\t\t{} MHz{}", frequency, in_use);
+        }
+        if let Some(noise) = survey.noise {
+            println!("\tnoise:\t\t\t\t{} dBm", noise);
+        }
+        for (name, value) in [
+            ("channel active time", survey.time),
+            ("channel busy time", survey.time_busy),
+            ("channel receive time", survey.time_rx),
+            ("channel transmit time", survey.time_tx),
+        ] {
+            if let Some(value) = value {
+                println!("\t{}:\t\t{} ms", name, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn event(client: &mut WifiClient) -> Result<(), Box<dyn Error>> {
+    for group in ["config", "scan", "regulatory", "mlme"] {
+        client.subscribe(group)?;
+    }
+    loop {
+        println!("{:?}", client.next_event()?);
+    }
+}
+
+fn trim_nul(bytes: &[u8]) -> &[u8] {
+    bytes.strip_suffix(&[0]).unwrap_or(bytes)
+}
+
+fn text(bytes: Option<&[u8]>) -> String {
+    String::from_utf8_lossy(bytes.map(trim_nul).unwrap_or_default()).into_owned()
+}
+
+fn format_mac(mac: &[u8]) -> String {
+    mac.iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
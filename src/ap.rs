@@ -0,0 +1,272 @@
+use crate::attr::{Nl80211Attr, Nl80211AuthType, Nl80211ChanWidth, Nl80211HiddenSsid};
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// Configuration of an access point, see [`Socket::start_ap`](crate::Socket::start_ap)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApConfig {
+    /// Beacon frame up to (but not including) the TIM element
+    pub beacon_head: Vec<u8>,
+    /// Beacon frame after the TIM element
+    pub beacon_tail: Vec<u8>,
+    /// Beacon interval in TU
+    pub beacon_interval: u32,
+    /// DTIM period in beacon intervals
+    pub dtim_period: u32,
+    /// SSID of the network
+    pub ssid: Vec<u8>,
+    /// How the SSID is hidden from beacons, the SSID element of `beacon_head` must match
+    /// (empty or zeroed), while probe responses still carry `ssid`
+    pub hidden_ssid: Option<Nl80211HiddenSsid>,
+    /// Control channel frequency (MHz), the current channel is used if unset
+    pub frequency: Option<u32>,
+    /// Channel width, defaults to 20 MHz without HT
+    pub channel_width: Option<Nl80211ChanWidth>,
+    /// Center frequency of the whole channel for 40 MHz and wider channels (MHz)
+    pub center_frequency1: Option<u32>,
+    /// Authentication type accepted from clients
+    pub auth_type: Option<Nl80211AuthType>,
+    /// Whether the network requires encryption
+    pub privacy: bool,
+    /// Disconnect clients which have been inactive for this long (seconds), the driver picks a
+    /// default if unset
+    pub inactivity_timeout: Option<u16>,
+    /// Stop the AP automatically when the socket that started it is closed
+    pub socket_owner: bool,
+}
+
+impl Default for ApConfig {
+    fn default() -> Self {
+        Self {
+            beacon_head: Vec::new(),
+            beacon_tail: Vec::new(),
+            beacon_interval: 100,
+            dtim_period: 2,
+            ssid: Vec::new(),
+            hidden_ssid: None,
+            frequency: None,
+            channel_width: None,
+            center_frequency1: None,
+            auth_type: None,
+            privacy: false,
+            inactivity_timeout: None,
+            socket_owner: false,
+        }
+    }
+}
+
+impl ApConfig {
+    pub(crate) fn attrs(
+        &self,
+        interface_index: i32,
+    ) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrBeaconHead,
+            self.beacon_head.as_slice(),
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrBeaconTail,
+            self.beacon_tail.as_slice(),
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrBeaconInterval,
+            self.beacon_interval,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrDtimPeriod,
+            self.dtim_period,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrSsid,
+            self.ssid.as_slice(),
+        )?);
+        if let Some(hidden_ssid) = self.hidden_ssid {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrHiddenSsid,
+                u32::from(u16::from(hidden_ssid)),
+            )?);
+        }
+        if let Some(frequency) = self.frequency {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWiphyFreq,
+                frequency,
+            )?);
+        }
+        if let Some(width) = self.channel_width {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrChannelWidth,
+                u32::from(u16::from(width)),
+            )?);
+        }
+        if let Some(center_frequency1) = self.center_frequency1 {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrCenterFreq1,
+                center_frequency1,
+            )?);
+        }
+        if let Some(auth_type) = self.auth_type {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)),
+            )?);
+        }
+        if self.privacy {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrPrivacy, ())?);
+        }
+        if let Some(timeout) = self.inactivity_timeout {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrInactivityTimeout,
+                timeout,
+            )?);
+        }
+        if self.socket_owner {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrSocketOwner, ())?);
+        }
+        Ok(attrs)
+    }
+}
+
+/// BSS parameters of a running access point, see [`Socket::set_bss`](crate::Socket::set_bss)
+///
+/// Unset fields are left unchanged.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BssConfig {
+    /// Prevent clients of the AP from communicating with each other
+    pub ap_isolate: Option<bool>,
+    /// Use CTS protection
+    pub cts_protection: Option<bool>,
+    /// Allow short preamble
+    pub short_preamble: Option<bool>,
+    /// Allow short slot time
+    pub short_slot_time: Option<bool>,
+}
+
+impl BssConfig {
+    pub(crate) fn attrs(
+        &self,
+        interface_index: i32,
+    ) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        for (attr, value) in [
+            (Nl80211Attr::AttrApIsolate, self.ap_isolate),
+            (Nl80211Attr::AttrBssCtsProt, self.cts_protection),
+            (Nl80211Attr::AttrBssShortPreamble, self.short_preamble),
+            (Nl80211Attr::AttrBssShortSlotTime, self.short_slot_time),
+        ] {
+            if let Some(value) = value {
+                attrs.push(Nlattr::new(false, false, attr, u8::from(value))?);
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test_ap {
+    use super::*;
+
+    #[test]
+    fn test_attrs() {
+        let config = ApConfig {
+            ssid: b"ap".to_vec(),
+            frequency: Some(5180),
+            channel_width: Some(Nl80211ChanWidth::ChanWidth80),
+            center_frequency1: Some(5210),
+            hidden_ssid: Some(Nl80211HiddenSsid::HiddenSsidZeroContents),
+            inactivity_timeout: Some(300),
+            socket_owner: true,
+            ..Default::default()
+        };
+
+        let attrs = config.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrBeaconInterval)
+                .unwrap(),
+            100
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrChannelWidth)
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrHiddenSsid)
+                .unwrap(),
+            2
+        );
+        assert!(attrs.get_attribute(Nl80211Attr::AttrSocketOwner).is_some());
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u16>(Nl80211Attr::AttrInactivityTimeout)
+                .unwrap(),
+            300
+        );
+        assert!(attrs.get_attribute(Nl80211Attr::AttrPrivacy).is_none());
+    }
+
+    #[test]
+    fn test_bss_attrs() {
+        let config = BssConfig {
+            ap_isolate: Some(true),
+            short_preamble: Some(false),
+            ..Default::default()
+        };
+
+        let attrs = config.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u8>(Nl80211Attr::AttrApIsolate)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u8>(Nl80211Attr::AttrBssShortPreamble)
+                .unwrap(),
+            0
+        );
+        assert!(attrs.get_attribute(Nl80211Attr::AttrBssCtsProt).is_none());
+    }
+}
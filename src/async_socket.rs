@@ -1,33 +1,75 @@
+use crate::monitor::{cookie_attrs, frame_attrs, new_interface_attrs, remain_on_channel_attrs};
+use crate::parse::{parse_attrs, ParseWarning};
+use crate::power::tx_power_attrs;
+use crate::socket::{is_events_lost, is_notification, is_reply, next_seq};
+use crate::survey::merge_surveys;
+use crate::txq::{txq_limits_attrs, txq_params_attrs};
+use crate::wiphy::{coverage_class_for_distance, merge_wiphys};
+use crate::ApConfig;
+use crate::Attrs;
 use crate::Bss;
+use crate::BssConfig;
+use crate::BssRef;
+use crate::ConnectParams;
+use crate::Event;
 use crate::Interface;
+use crate::Mbm;
 use crate::Nl80211Attr;
 use crate::Nl80211Cmd;
+use crate::Nl80211Iftype;
+use crate::Nl80211MntrFlags;
+use crate::ReasonCode;
+use crate::ScanParams;
+use crate::SchedScanParams;
 use crate::Socket;
+use crate::SocketBuilder;
 use crate::Station;
-use crate::NL_80211_GENL_VERSION;
+use crate::Survey;
+use crate::TxPower;
+use crate::TxqLimits;
+use crate::TxqParams;
+use crate::Wiphy;
 
 use neli::consts::genl::{CtrlAttr, CtrlCmd};
 use neli::consts::{nl::GenlId, nl::NlmF, nl::NlmFFlags, nl::Nlmsg};
-use neli::err::NlError;
+use neli::err::{DeError, NlError};
 use neli::genl::{Genlmsghdr, Nlattr};
 use neli::nl::{NlPayload, Nlmsghdr};
 use neli::socket::tokio::NlSocket;
-use neli::types::GenlBuffer;
+use neli::types::{Buffer, GenlBuffer};
+
+use std::collections::VecDeque;
+use std::io;
 
 /// A generic netlink socket to send commands and receive messages
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
 pub struct AsyncSocket {
     sock: NlSocket,
     family_id: u16,
+    pid: u32,
+    seq: u32,
+    notifications: VecDeque<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>,
+    options: SocketBuilder,
+    warnings: Vec<ParseWarning>,
+    #[cfg(feature = "rtnetlink")]
+    pub(crate) link: Option<NlSocket>,
 }
 
 impl TryFrom<Socket> for AsyncSocket {
     type Error = std::io::Error;
 
-    fn try_from(from: Socket) -> Result<Self, Self::Error> {
+    /// Keeps the protocol version and parse mode of the socket
+    fn try_from(mut from: Socket) -> Result<Self, Self::Error> {
         Ok(Self {
+            options: from.options().clone(),
+            warnings: from.take_warnings(),
+            pid: from.sock.pid()?,
+            seq: from.seq,
             sock: NlSocket::new(from.sock)?,
             family_id: from.family_id,
+            notifications: std::mem::take(&mut from.notifications),
+            #[cfg(feature = "rtnetlink")]
+            link: None,
         })
     }
 }
@@ -55,44 +97,17 @@ impl AsyncSocket {
     /// # };
     ///```
     pub async fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetInterface,
-            NL_80211_GENL_VERSION,
-            GenlBuffer::new(),
-        );
-
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
-
-        self.sock.send(&nlhdr).await?;
-
-        let mut buf = Vec::new();
+        let replies = self
+            .dump(Nl80211Cmd::CmdGetInterface, GenlBuffer::new())
+            .await?;
         let mut interfaces = Vec::new();
-
-        loop {
-            let res = self
-                .sock
-                .recv::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf)
-                .await?;
-            for response in res {
-                match response.nl_type {
-                    Nlmsg::Noop => (),
-                    Nlmsg::Error => panic!("Error"),
-                    Nlmsg::Done => return Ok(interfaces),
-                    _ => {
-                        let handle = response.nl_payload.get_payload().unwrap().get_attr_handle();
-                        interfaces.push(handle.try_into()?);
-                    }
-                };
-            }
+        for reply in replies {
+            interfaces.push(self.parse(&reply)?);
         }
+        #[cfg(feature = "rtnetlink")]
+        self.fill_link_states(&mut interfaces).await?;
+
+        Ok(interfaces)
     }
 
     /// Get access point information for a specific interface
@@ -118,76 +133,566 @@ impl AsyncSocket {
     /// # }
     ///```
     pub async fn get_station_info(&mut self, interface_index: i32) -> Result<Station, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetStation,
-            NL_80211_GENL_VERSION,
-            {
-                let mut attrs = GenlBuffer::new();
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
-                attrs
-            },
-        );
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetStation, attrs).await?;
+        match replies.last() {
+            Some(reply) => Ok(self.parse(reply)?),
+            None => Ok(Station::default()),
+        }
+    }
 
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+    pub async fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetScan, attrs).await?;
+        let mut retval = Vec::new();
+        for reply in replies {
+            retval.push(self.parse(&reply)?);
+        }
 
-        self.sock.send(&nlhdr).await?;
+        Ok(retval)
+    }
+
+    /// Call `f` with each scan result of a specific interface, borrowing the BSSID and
+    /// information elements from the reply instead of copying them as
+    /// [`get_bss_info`](Self::get_bss_info) does
+    pub async fn for_each_bss<F>(&mut self, interface_index: i32, mut f: F) -> Result<(), NlError>
+    where
+        F: FnMut(BssRef<'_>),
+    {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetScan, attrs).await?;
+        for reply in replies {
+            let attrs = reply.get_attr_handle();
+            f(BssRef::try_from(&attrs)?);
+        }
+
+        Ok(())
+    }
+
+    /// Start a scan on an interface, see [`Socket::trigger_scan`]
+    pub async fn trigger_scan(
+        &mut self,
+        interface_index: i32,
+        params: &ScanParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdTriggerScan, attrs).await
+    }
+
+    /// Start a scheduled scan on an interface, see [`Socket::start_sched_scan`]
+    pub async fn start_sched_scan(
+        &mut self,
+        interface_index: i32,
+        params: &SchedScanParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdStartSchedScan, attrs).await
+    }
+
+    /// Stop the scheduled scan of an interface
+    pub async fn stop_sched_scan(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdStopSchedScan, attrs).await
+    }
+
+    /// Get information for all your wiphys
+    pub async fn get_wiphys(&mut self) -> Result<Vec<Wiphy>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrSplitWiphyDump,
+            (),
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetWiphy, attrs).await?;
+        let mut parts = Vec::new();
+        for reply in replies {
+            parts.push(self.parse(&reply)?);
+        }
+
+        Ok(merge_wiphys(parts))
+    }
+
+    /// Get the wiphy with the given name, e.g. "phy0"
+    pub async fn get_wiphy_by_name(&mut self, name: &str) -> Result<Option<Wiphy>, NlError> {
+        let wiphys = self.get_wiphys().await?;
+        Ok(wiphys
+            .into_iter()
+            .find(|wiphy| wiphy.name.as_deref() == Some(name)))
+    }
+
+    /// Get the channel survey of the wiphy of a specific interface
+    pub async fn get_survey(&mut self, interface_index: i32) -> Result<Vec<Survey>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetSurvey, attrs).await?;
+        let mut surveys = Vec::new();
+        for reply in replies {
+            surveys.push(self.parse(&reply)?);
+        }
+
+        Ok(surveys)
+    }
+
+    /// Get the channel survey of a wiphy, with one entry per frequency, see
+    /// [`Socket::get_survey_for_phy`]
+    pub async fn get_survey_for_phy(&mut self, phy: u32) -> Result<Vec<Survey>, NlError> {
+        let index = self
+            .get_interfaces_info()
+            .await?
+            .into_iter()
+            .find(|interface| interface.phy == Some(phy))
+            .and_then(|interface| interface.index)
+            .ok_or_else(|| NlError::msg("The wiphy has no network interface"))?;
+        Ok(merge_surveys(self.get_survey(index).await?))
+    }
+
+    /// Tune the interface to a frequency (MHz), optionally with a KHz offset for S1G channels.
+    ///
+    /// This is mostly useful for monitor interfaces, other interface types usually select
+    /// their channel on their own.
+    pub async fn set_frequency(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        frequency_offset: Option<u32>,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyFreq,
+            frequency,
+        )?);
+        if let Some(offset) = frequency_offset {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWiphyFreqOffset,
+                offset,
+            )?);
+        }
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Connect the interface to a network
+    ///
+    /// This only asks the driver to start connecting, the outcome is reported as a
+    /// `CmdConnect` event on the "mlme" multicast group.
+    pub async fn connect_network(
+        &mut self,
+        interface_index: i32,
+        params: &ConnectParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdConnect, attrs).await
+    }
+
+    /// Disconnect the interface from the current network
+    pub async fn disconnect(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrReasonCode,
+            u16::from(ReasonCode::DeauthLeaving),
+        )?);
+        self.execute(Nl80211Cmd::CmdDisconnect, attrs).await
+    }
 
+    /// Create a new virtual interface on a wiphy
+    ///
+    /// If `socket_owner` is set, the kernel removes the interface once the socket is closed,
+    /// including when the process crashes.
+    pub async fn new_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        let attrs = new_interface_attrs(phy, name, iftype, socket_owner, &[])?;
+        self.create_interface(attrs).await
+    }
+
+    /// Create a monitor interface on a wiphy, with monitor flags such as
+    /// [`MntrFlagActive`](Nl80211MntrFlags::MntrFlagActive) to acknowledge frames sent to the
+    /// interface's address, see [`InjectionSocket`](crate::InjectionSocket) for frame injection
+    pub async fn new_monitor_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        flags: &[Nl80211MntrFlags],
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        let iftype = Nl80211Iftype::IftypeMonitor;
+        let attrs = new_interface_attrs(phy, name, iftype, socket_owner, flags)?;
+        self.create_interface(attrs).await
+    }
+
+    async fn create_interface(
+        &mut self,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Interface, NlError> {
+        let replies = self.request(Nl80211Cmd::CmdNewInterface, attrs).await?;
+        match replies.first() {
+            Some(reply) => Ok(self.parse(reply)?),
+            None => Err(NlError::msg("No interface was returned by the kernel")),
+        }
+    }
+
+    /// Transmit a management frame, starting with its 802.11 header, on a non-monitor interface
+    ///
+    /// The frame is sent on `frequency` if set, going off-channel if needed, and on the
+    /// operating channel otherwise. With `wait_for_ack`, the kernel returns a cookie matching
+    /// the `NL80211_CMD_FRAME_TX_STATUS` notification of the frame.
+    pub async fn send_frame(
+        &mut self,
+        interface_index: i32,
+        frequency: Option<u32>,
+        frame: &[u8],
+        wait_for_ack: bool,
+    ) -> Result<Option<u64>, NlError> {
+        let attrs = frame_attrs(interface_index, frequency, frame, wait_for_ack)?;
+        let replies = self.request(Nl80211Cmd::CmdFrame, attrs).await?;
+        let cookie = replies.first().and_then(|reply| {
+            reply
+                .get_attr_handle()
+                .get_attr_payload_as::<u64>(Nl80211Attr::AttrCookie)
+                .ok()
+        });
+        Ok(cookie)
+    }
+
+    /// Stay on a channel for a while on an interface, see [`Socket::remain_on_channel`]
+    pub async fn remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        duration: u32,
+    ) -> Result<u64, NlError> {
+        let attrs = remain_on_channel_attrs(interface_index, frequency, duration)?;
+        let replies = self.request(Nl80211Cmd::CmdRemainOnChannel, attrs).await?;
+        replies
+            .first()
+            .and_then(|reply| {
+                reply
+                    .get_attr_handle()
+                    .get_attr_payload_as::<u64>(Nl80211Attr::AttrCookie)
+                    .ok()
+            })
+            .ok_or_else(|| NlError::msg("No cookie was returned by the kernel"))
+    }
+
+    /// End a remain-on-channel early, see [`Socket::cancel_remain_on_channel`]
+    pub async fn cancel_remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        cookie: u64,
+    ) -> Result<(), NlError> {
+        let attrs = cookie_attrs(interface_index, cookie)?;
+        self.execute(Nl80211Cmd::CmdCancelRemainOnChannel, attrs)
+            .await
+    }
+
+    /// Remove a virtual interface
+    pub async fn del_interface(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdDelInterface, attrs).await
+    }
+
+    /// Start operating as an access point on an AP interface
+    pub async fn start_ap(
+        &mut self,
+        interface_index: i32,
+        config: &ApConfig,
+    ) -> Result<(), NlError> {
+        let attrs = config.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdStartAp, attrs).await
+    }
+
+    /// Stop operating as an access point
+    pub async fn stop_ap(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdStopAp, attrs).await
+    }
+
+    /// Change the BSS parameters of an AP or P2P GO interface, such as client isolation
+    pub async fn set_bss(
+        &mut self,
+        interface_index: i32,
+        config: &BssConfig,
+    ) -> Result<(), NlError> {
+        let attrs = config.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdSetBss, attrs).await
+    }
+
+    /// Get the transmit power level of an interface
+    pub async fn get_tx_power(&mut self, interface_index: i32) -> Result<Option<Mbm>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.request(Nl80211Cmd::CmdGetInterface, attrs).await?;
+        match replies.first() {
+            Some(reply) => Ok(self.parse::<Interface>(reply)?.tx_power()),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the transmit power of an interface, or of its whole wiphy for drivers which don't
+    /// support per interface settings
+    pub async fn set_tx_power(
+        &mut self,
+        interface_index: i32,
+        power: TxPower,
+    ) -> Result<(), NlError> {
+        let attrs = tx_power_attrs(interface_index, power)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Set the coverage class of a wiphy, to extend its slot time and ACK timeout for long
+    /// distance links
+    pub async fn set_coverage_class(
+        &mut self,
+        phy: u32,
+        coverage_class: u8,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyCoverageClass,
+            coverage_class,
+        )?);
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Set the coverage class of a wiphy for a link of `distance` meters, see
+    /// [`coverage_class_for_distance`]
+    pub async fn set_distance(&mut self, phy: u32, distance: u32) -> Result<(), NlError> {
+        self.set_coverage_class(phy, coverage_class_for_distance(distance))
+            .await
+    }
+
+    /// Set the EDCA parameters of transmit queues of an AP or P2P GO interface
+    pub async fn set_txq_params(
+        &mut self,
+        interface_index: i32,
+        params: &[TxqParams],
+    ) -> Result<(), NlError> {
+        let attrs = txq_params_attrs(interface_index, params)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Change the limits of the transmit queues of a wiphy
+    pub async fn set_txq_limits(&mut self, phy: u32, limits: &TxqLimits) -> Result<(), NlError> {
+        let attrs = txq_limits_attrs(phy, limits)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Receive management frames of the given type whose body starts with `frame_match`
+    /// (e.g. [`FRAME_TYPE_PROBE_REQ`](crate::FRAME_TYPE_PROBE_REQ) with an empty match) on
+    /// this socket, as [`Event::Frame`] events.
+    ///
+    /// The registration lasts as long as the socket is open.
+    pub async fn register_frame(
+        &mut self,
+        interface_index: i32,
+        frame_type: u16,
+        frame_match: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrFrameType,
+            frame_type,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrFrameMatch,
+            frame_match,
+        )?);
+        self.execute(Nl80211Cmd::CmdRegisterFrame, attrs).await
+    }
+
+    /// Receive the action frames of a category on this socket, see
+    /// [`Socket::register_action_frames`]
+    pub async fn register_action_frames(
+        &mut self,
+        interface_index: i32,
+        category: u8,
+    ) -> Result<(), NlError> {
+        self.register_frame(interface_index, crate::FRAME_TYPE_ACTION, &[category])
+            .await
+    }
+
+    /// Receive the beacons of other BSSes heard by the wiphy on this socket, as [`Event::Frame`]
+    /// events.
+    ///
+    /// The registration lasts as long as the socket is open.
+    pub async fn register_beacons(&mut self, phy: u32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+        self.execute(Nl80211Cmd::CmdRegisterBeacons, attrs).await
+    }
+
+    /// Wait for the next event sent to this socket
+    ///
+    /// Multicast groups have to be joined with [`Socket::subscribe`] before converting the
+    /// socket into an [`AsyncSocket`]. Dropped events are reported as [`Event::EventsLost`].
+    pub async fn next_event(&mut self) -> Result<Event, NlError> {
         let mut buf = Vec::new();
-        let mut retval = None;
 
         loop {
-            let res = self
+            if let Some(msg) = self.notifications.pop_front() {
+                return Ok((&msg).try_into()?);
+            }
+            let res = match self
                 .sock
                 .recv::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf)
-                .await?;
+                .await
+            {
+                Err(DeError::Wrapped(err)) if is_events_lost(&err) => return Ok(Event::EventsLost),
+                res => res?,
+            };
             for response in res {
-                match response.nl_type {
-                    Nlmsg::Noop => (),
-                    Nlmsg::Error => panic!("Error"),
-                    Nlmsg::Done => return Ok(retval.unwrap_or_default()),
-                    _ => {
-                        retval = Some(
-                            response
-                                .nl_payload
-                                .get_payload()
-                                .unwrap()
-                                .get_attr_handle()
-                                .try_into()?,
-                        );
-                    }
-                };
+                if !is_notification(&response) {
+                    continue;
+                }
+                if let NlPayload::Payload(msg) = response.nl_payload {
+                    self.notifications.push_back(msg);
+                }
             }
         }
     }
 
-    pub async fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetScan,
-            NL_80211_GENL_VERSION,
-            {
-                let mut attrs = GenlBuffer::new();
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
-                attrs
-            },
-        );
+    /// Options the socket was created with, to open more sockets alike
+    pub(crate) fn options(&self) -> &SocketBuilder {
+        &self.options
+    }
+
+    /// Take the warnings about attributes skipped since the last call, see
+    /// [`SocketBuilder::lenient`](crate::SocketBuilder::lenient)
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Parse the attributes of a reply, skipping the malformed ones in lenient mode
+    fn parse<T>(&mut self, msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>) -> Result<T, DeError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        parse_attrs(msg, self.options.is_lenient(), &mut self.warnings)
+    }
+
+    /// Send a command and wait for the kernel to acknowledge it
+    async fn execute(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), NlError> {
+        self.request(cmd, attrs).await.map(drop)
+    }
+
+    /// Send a command and collect the replies until the kernel acknowledges it
+    async fn request(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        self.transact(cmd, attrs, &[NlmF::Request, NlmF::Ack]).await
+    }
+
+    /// Send a dump request and collect the replies
+    async fn dump(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        self.transact(cmd, attrs, &[NlmF::Request, NlmF::Dump])
+            .await
+    }
+
+    async fn transact(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+        flags: &[NlmF],
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        let seq = next_seq(&mut self.seq);
+        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(cmd, self.options.version(), attrs);
 
         let nlhdr = {
             let len = None;
             let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
+            let flags = NlmFFlags::new(flags);
+            let seq = Some(seq);
             let pid = None;
             let payload = NlPayload::Payload(msghdr);
             Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
@@ -196,7 +701,7 @@ impl AsyncSocket {
         self.sock.send(&nlhdr).await?;
 
         let mut buf = Vec::new();
-        let mut retval = Vec::new();
+        let mut replies = Vec::new();
 
         loop {
             let res = self
@@ -204,20 +709,23 @@ impl AsyncSocket {
                 .recv::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf)
                 .await?;
             for response in res {
-                match response.nl_type {
-                    Nlmsg::Noop => (),
-                    Nlmsg::Error => panic!("Error"),
-                    Nlmsg::Done => return Ok(retval),
-                    _ => {
-                        retval.push(
-                            response
-                                .nl_payload
-                                .get_payload()
-                                .unwrap()
-                                .get_attr_handle()
-                                .try_into()?,
-                        );
+                if !is_reply(&response, seq, self.pid) {
+                    // Kept for `next_event`, late replies to a previous request are dropped
+                    if is_notification(&response) {
+                        if let NlPayload::Payload(msg) = response.nl_payload {
+                            self.notifications.push_back(msg);
+                        }
+                    }
+                    continue;
+                }
+                match response.nl_payload {
+                    NlPayload::Payload(msg) => replies.push(msg),
+                    NlPayload::Ack(_) => return Ok(replies),
+                    NlPayload::Err(err) => {
+                        return Err(io::Error::from_raw_os_error(-err.error).into())
                     }
+                    _ if response.nl_type == Nlmsg::Done => return Ok(replies),
+                    _ => (),
                 }
             }
         }
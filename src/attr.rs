@@ -1,11 +1,98 @@
-use neli::attr::AttrHandle;
+use neli::attr::{AttrHandle, Attribute};
 use neli::consts::genl::NlAttrType;
+use neli::err::DeError;
 use neli::genl::Nlattr;
 use neli::types::{Buffer, GenlBuffer};
+use neli::{FromBytes, TypeSize};
 use neli_proc_macros::neli_enum;
 
 pub type Attrs<'a, T> = AttrHandle<'a, GenlBuffer<T, Buffer>, Nlattr<T, Buffer>>;
 
+/// Length checked access to attribute payloads
+///
+/// `get_payload_as` reads as many bytes as the type needs, ignoring any trailing data, so a
+/// malformed attribute would silently be truncated.
+pub(crate) trait PayloadExt {
+    /// Deserialize the payload, failing if its length differs from the size of the type
+    fn get_payload_exact<R>(&self) -> Result<R, DeError>
+    where
+        R: for<'a> FromBytes<'a> + TypeSize;
+
+    /// Get the payload as a MAC address, failing if it isn't 6 bytes long
+    fn get_mac(&self) -> Result<Vec<u8>, DeError>;
+}
+
+impl<T: NlAttrType> PayloadExt for Nlattr<T, Buffer> {
+    fn get_payload_exact<R>(&self) -> Result<R, DeError>
+    where
+        R: for<'a> FromBytes<'a> + TypeSize,
+    {
+        check_payload_len(self, R::type_size())?;
+        self.get_payload_as()
+    }
+
+    fn get_mac(&self) -> Result<Vec<u8>, DeError> {
+        check_payload_len(self, 6)?;
+        Ok(self.nla_payload.as_ref().to_vec())
+    }
+}
+
+/// Nested attributes parsed in place, yielding the type and payload of each attribute without
+/// copying it, unlike `get_attr_handle`
+pub(crate) struct NestedAttrs<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> NestedAttrs<'a> {
+    pub(crate) fn new(payload: &'a [u8]) -> Self {
+        Self { bytes: payload }
+    }
+}
+
+impl<'a> Iterator for NestedAttrs<'a> {
+    type Item = Result<(u16, &'a [u8]), DeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        const HEADER_LEN: usize = 4;
+        const TYPE_MASK: u16 = 0x3fff;
+
+        let header = self.bytes.get(..HEADER_LEN)?;
+        let len = usize::from(u16::from_ne_bytes([header[0], header[1]]));
+        let nla_type = u16::from_ne_bytes([header[2], header[3]]) & TYPE_MASK;
+        if len < HEADER_LEN || len > self.bytes.len() {
+            self.bytes = &[];
+            return Some(Err(DeError::UnexpectedEOB));
+        }
+        let payload = &self.bytes[HEADER_LEN..len];
+        let aligned = (len + 3) & !3;
+        self.bytes = self.bytes.get(aligned..).unwrap_or_default();
+        Some(Ok((nla_type, payload)))
+    }
+}
+
+/// Convert a payload to a fixed size array, failing if its length differs
+pub(crate) fn exact_payload<const N: usize>(payload: &[u8]) -> Result<[u8; N], DeError> {
+    payload.try_into().map_err(|_| {
+        DeError::new(format!(
+            "Attribute has a payload of {} bytes, expected {}",
+            payload.len(),
+            N
+        ))
+    })
+}
+
+fn check_payload_len<T: NlAttrType>(attr: &Nlattr<T, Buffer>, len: usize) -> Result<(), DeError> {
+    let actual = attr.nla_payload.as_ref().len();
+    if actual == len {
+        Ok(())
+    } else {
+        Err(DeError::new(format!(
+            "Attribute {:?} has a payload of {} bytes, expected {}",
+            attr.nla_type.nla_type, actual, len
+        )))
+    }
+}
+
 #[neli_enum(serialized_type = "u16")]
 pub enum NlaNested {
     Unspec = 0,
@@ -17,6 +104,7 @@ impl NlAttrType for NlaNested {}
 ///
 /// Enumeration from nl80211/nl80211.h:1929
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Attr {
     AttrUnspec = 0,
     AttrWiphy = 1,
@@ -260,6 +348,98 @@ pub enum Nl80211Attr {
     AttrNanDual = 239,
     AttrNanFunc = 240,
     AttrNanMatch = 241,
+    AttrFilsKek = 242,
+    AttrFilsNonces = 243,
+    AttrMulticastToUnicastEnabled = 244,
+    AttrBssid = 245,
+    AttrSchedScanRelativeRssi = 246,
+    AttrSchedScanRssiAdjust = 247,
+    AttrTimeoutReason = 248,
+    AttrFilsErpUsername = 249,
+    AttrFilsErpRealm = 250,
+    AttrFilsErpNextSeqNum = 251,
+    AttrFilsErpRrk = 252,
+    AttrFilsCacheId = 253,
+    AttrPmk = 254,
+    AttrSchedScanMulti = 255,
+    AttrSchedScanMaxReqs = 256,
+    AttrWant1x4wayHs = 257,
+    AttrPmkr0Name = 258,
+    AttrPortAuthorized = 259,
+    AttrExternalAuthAction = 260,
+    AttrExternalAuthSupport = 261,
+    AttrNss = 262,
+    AttrAckSignal = 263,
+    AttrControlPortOverNl80211 = 264,
+    AttrTxqStats = 265,
+    AttrTxqLimit = 266,
+    AttrTxqMemoryLimit = 267,
+    AttrTxqQuantum = 268,
+    AttrHeCapability = 269,
+    AttrFtmResponder = 270,
+    AttrFtmResponderStats = 271,
+    AttrTimeout = 272,
+    AttrPeerMeasurements = 273,
+    AttrAirtimeWeight = 274,
+    AttrStaTxPowerSetting = 275,
+    AttrStaTxPower = 276,
+    AttrSaePassword = 277,
+    AttrTwtResponder = 278,
+    AttrHeObssPd = 279,
+    AttrWiphyEdmgChannels = 280,
+    AttrWiphyEdmgBwConfig = 281,
+    AttrVlanId = 282,
+    AttrHeBssColor = 283,
+    AttrIftypeAkmSuites = 284,
+    AttrTidConfig = 285,
+    AttrControlPortNoPreauth = 286,
+    AttrPmkLifetime = 287,
+    AttrPmkReauthThreshold = 288,
+    AttrReceiveMulticast = 289,
+    AttrWiphyFreqOffset = 290,
+    AttrCenterFreq1Offset = 291,
+    AttrScanFreqKhz = 292,
+    AttrHe6ghzCapability = 293,
+    AttrFilsDiscovery = 294,
+    AttrUnsolBcastProbeResp = 295,
+    AttrS1gCapability = 296,
+    AttrS1gCapabilityMask = 297,
+    AttrSaePwe = 298,
+    AttrReconnectRequested = 299,
+    AttrSarSpec = 300,
+    AttrDisableHe = 301,
+    AttrObssColorBitmap = 302,
+    AttrColorChangeCount = 303,
+    AttrColorChangeColor = 304,
+    AttrColorChangeElems = 305,
+    AttrMbssidConfig = 306,
+    AttrMbssidElems = 307,
+    AttrRadarBackground = 308,
+    AttrApSettingsFlags = 309,
+    AttrEhtCapability = 310,
+    AttrDisableEht = 311,
+    AttrMloLinks = 312,
+    AttrMloLinkId = 313,
+    AttrMldAddr = 314,
+    AttrMloSupport = 315,
+    AttrMaxNumAkmSuites = 316,
+    AttrEmlCapability = 317,
+    AttrMldCapaAndOps = 318,
+    AttrTxHwTimestamp = 319,
+    AttrRxHwTimestamp = 320,
+    AttrTdBitmap = 321,
+    AttrPunctBitmap = 322,
+    AttrAssocSppAmsdu = 323,
+    AttrWiphyRadios = 324,
+    AttrWiphyInterfaceCombinations = 325,
+    AttrVifRadioMask = 326,
+    AttrSupportedSelectors = 327,
+    AttrMloReconfRemLinks = 328,
+    AttrEpcs = 329,
+    AttrAssocMldExtCapaOps = 330,
+    AttrWiphyRadioIndex = 331,
+    AttrS1gLongBeaconPeriod = 332,
+    AttrS1gShortBeacon = 333,
 }
 
 impl NlAttrType for Nl80211Attr {}
@@ -268,6 +448,7 @@ impl NlAttrType for Nl80211Attr {}
 ///
 /// Enumeration from nl80211/nl80211.h:2384
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Iftype {
     IftypeUnspecified = 0,
     IftypeAdhoc = 1,
@@ -288,6 +469,7 @@ impl NlAttrType for Nl80211Iftype {}
 
 /// nl80211StaFlags as declared in nl80211/nl80211.h:2428
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211StaFlags {
     StaFlagInvalid = 0,
     StaFlagAuthorized = 1,
@@ -297,6 +479,7 @@ pub enum Nl80211StaFlags {
     StaFlagAuthenticated = 5,
     StaFlagTdlsPeer = 6,
     StaFlagAssociated = 7,
+    StaFlagSppAmsdu = 8,
 }
 
 impl NlAttrType for Nl80211StaFlags {}
@@ -305,6 +488,7 @@ impl NlAttrType for Nl80211StaFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:2450
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211StaP2pPsStatus {
     P2pPsUnsupported = 0,
     P2pPsSupported = 1,
@@ -316,6 +500,7 @@ impl NlAttrType for Nl80211StaP2pPsStatus {}
 ///
 /// Enumeration from nl80211/nl80211.h:2505
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RateInfo {
     RateInfoInvalid = 0,
     RateInfoBitrate = 1,
@@ -330,6 +515,23 @@ pub enum Nl80211RateInfo {
     RateInfo160MhzWidth = 10,
     RateInfo10MhzWidth = 11,
     RateInfo5MhzWidth = 12,
+    RateInfoHeMcs = 13,
+    RateInfoHeNss = 14,
+    RateInfoHeGi = 15,
+    RateInfoHeDcm = 16,
+    RateInfoHeRuAlloc = 17,
+    RateInfo320MhzWidth = 18,
+    RateInfoEhtMcs = 19,
+    RateInfoEhtNss = 20,
+    RateInfoEhtGi = 21,
+    RateInfoEhtRuAlloc = 22,
+    RateInfoS1gMcs = 23,
+    RateInfoS1gNss = 24,
+    RateInfo1MhzWidth = 25,
+    RateInfo2MhzWidth = 26,
+    RateInfo4MhzWidth = 27,
+    RateInfo8MhzWidth = 28,
+    RateInfo16MhzWidth = 29,
 }
 
 impl NlAttrType for Nl80211RateInfo {}
@@ -338,6 +540,7 @@ impl NlAttrType for Nl80211RateInfo {}
 ///
 /// Enumeration from nl80211/nl80211.h:2542
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211StaBssParam {
     StaBssParamInvalid = 0,
     StaBssParamCtsProt = 1,
@@ -353,6 +556,7 @@ impl NlAttrType for Nl80211StaBssParam {}
 ///
 /// Enumeration from nl80211/nl80211.h:2620
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211StaInfo {
     StaInfoInvalid = 0,
     StaInfoInactiveTime = 1,
@@ -388,6 +592,16 @@ pub enum Nl80211StaInfo {
     StaInfoTidStats = 31,
     StaInfoRxDuration = 32,
     StaInfoPad = 33,
+    StaInfoAckSignal = 34,
+    StaInfoAckSignalAvg = 35,
+    StaInfoRxMpdus = 36,
+    StaInfoFcsErrorCount = 37,
+    StaInfoConnectedToGate = 38,
+    StaInfoTxDuration = 39,
+    StaInfoAirtimeWeight = 40,
+    StaInfoAirtimeLinkMetric = 41,
+    StaInfoAssocAtBoottime = 42,
+    StaInfoConnectedToAs = 43,
 }
 
 impl NlAttrType for Nl80211StaInfo {}
@@ -396,6 +610,7 @@ impl NlAttrType for Nl80211StaInfo {}
 ///
 /// Enumeration from nl80211/nl80211.h:2675
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TidStats {
     TidStatsInvalid = 0,
     TidStatsRxMsdu = 1,
@@ -411,6 +626,7 @@ impl NlAttrType for Nl80211TidStats {}
 ///
 /// Enumeration from nl80211/nl80211.h:2697
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MpathFlags {
     MpathFlagActive = 1 << 0,
     MpathFlagResolving = 1 << 1,
@@ -425,6 +641,7 @@ impl NlAttrType for Nl80211MpathFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:2697
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MpathInfo {
     MpathInfoInvalid = 0,
     MpathInfoFrameQlen = 1,
@@ -442,6 +659,7 @@ impl NlAttrType for Nl80211MpathInfo {}
 ///
 /// Enumeration from nl80211/nl80211.h:2757
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211BandAttr {
     BandAttrInvalid = 0,
     BandAttrFreqs = 1,
@@ -452,6 +670,11 @@ pub enum Nl80211BandAttr {
     BandAttrHtAmpduDensity = 6,
     BandAttrVhtMcsSet = 7,
     BandAttrVhtCapa = 8,
+    BandAttrIftypeData = 9,
+    BandAttrEdmgChannels = 10,
+    BandAttrEdmgBwConfig = 11,
+    BandAttrS1gMcsNssSet = 12,
+    BandAttrS1gCapa = 13,
 }
 
 impl NlAttrType for Nl80211BandAttr {}
@@ -460,6 +683,7 @@ impl NlAttrType for Nl80211BandAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:2833
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211FrequencyAttr {
     FrequencyAttrInvalid = 0,
     FrequencyAttrFreq = 1,
@@ -479,6 +703,22 @@ pub enum Nl80211FrequencyAttr {
     FrequencyAttrIrConcurrent = 15,
     FrequencyAttrNo20mhz = 16,
     FrequencyAttrNo10mhz = 17,
+    FrequencyAttrWmm = 18,
+    FrequencyAttrNoHe = 19,
+    FrequencyAttrOffset = 20,
+    FrequencyAttr1mhz = 21,
+    FrequencyAttr2mhz = 22,
+    FrequencyAttr4mhz = 23,
+    FrequencyAttr8mhz = 24,
+    FrequencyAttr16mhz = 25,
+    FrequencyAttrNo320mhz = 26,
+    FrequencyAttrNoEht = 27,
+    FrequencyAttrPsd = 28,
+    FrequencyAttrDfsConcurrent = 29,
+    FrequencyAttrNo6ghzVlpClient = 30,
+    FrequencyAttrNo6ghzAfcClient = 31,
+    FrequencyAttrCanMonitor = 32,
+    FrequencyAttrAllow6ghzVlpAp = 33,
 }
 
 impl NlAttrType for Nl80211FrequencyAttr {}
@@ -487,6 +727,7 @@ impl NlAttrType for Nl80211FrequencyAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:2873
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211BitrateAttr {
     BitrateAttrInvalid = 0,
     BitrateAttrRate = 1,
@@ -499,6 +740,7 @@ impl NlAttrType for Nl80211BitrateAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:2899
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RegInitiator {
     RegdomSetByCore = 0,
     RegdomSetByUser = 1,
@@ -512,6 +754,7 @@ impl NlAttrType for Nl80211RegInitiator {}
 ///
 /// Enumeration from nl80211/nl80211.h:2922
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RegType {
     RegdomTypeCountry = 0,
     RegdomTypeWorld = 1,
@@ -525,6 +768,7 @@ impl NlAttrType for Nl80211RegType {}
 ///
 /// Enumeration from nl80211/nl80211.h:2954
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RegRuleAttr {
     RegRuleAttrInvalid = 0,
     AttrRegRuleFlags = 1,
@@ -542,6 +786,7 @@ impl NlAttrType for Nl80211RegRuleAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:2989
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211SchedScanMatchAttr {
     SchedScanMatchAttrInvalid = 0,
     SchedScanMatchAttrSsid = 1,
@@ -554,6 +799,7 @@ impl NlAttrType for Nl80211SchedScanMatchAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:3026
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RegRuleFlags {
     RrfNoOfdm = 1 << 0,
     RrfNoCck = 1 << 1,
@@ -577,6 +823,7 @@ impl NlAttrType for Nl80211RegRuleFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:3061
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211DfsRegions {
     DfsUnset = 0,
     DfsFcc = 1,
@@ -590,6 +837,7 @@ impl NlAttrType for Nl80211DfsRegions {}
 ///
 /// Enumeration from nl80211/nl80211.h:3085
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211UserRegHintType {
     UserRegHintUser = 0,
     UserRegHintCellBase = 1,
@@ -602,6 +850,7 @@ impl NlAttrType for Nl80211UserRegHintType {}
 ///
 /// Enumeration from nl80211/nl80211.h:3118
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211SurveyInfo {
     SurveyInfoInvalid = 0,
     SurveyInfoFrequency = 1,
@@ -614,6 +863,8 @@ pub enum Nl80211SurveyInfo {
     SurveyInfoTimeTx = 8,
     SurveyInfoTimeScan = 9,
     SurveyInfoPad = 10,
+    SurveyInfoTimeBssRx = 11,
+    SurveyInfoFrequencyOffset = 12,
 }
 
 impl NlAttrType for Nl80211SurveyInfo {}
@@ -622,6 +873,7 @@ impl NlAttrType for Nl80211SurveyInfo {}
 ///
 /// Enumeration from nl80211/nl80211.h:3162
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MntrFlags {
     MntrFlagInvalid = 0,
     MntrFlagFcsfail = 1,
@@ -638,6 +890,7 @@ impl NlAttrType for Nl80211MntrFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:3194
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MeshPowerMode {
     MeshPowerUnknown = 0,
     MeshPowerActive = 1,
@@ -651,6 +904,7 @@ impl NlAttrType for Nl80211MeshPowerMode {}
 ///
 /// Enumeration from nl80211/nl80211.h:3312
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MeshconfParams {
     MeshconfInvalid = 0,
     MeshconfRetryTimeout = 1,
@@ -689,6 +943,7 @@ impl NlAttrType for Nl80211MeshconfParams {}
 ///
 /// Enumeration from nl80211/nl80211.h:3397
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211MeshSetupParams {
     MeshSetupInvalid = 0,
     MeshSetupEnableVendorPathSel = 1,
@@ -707,6 +962,7 @@ impl NlAttrType for Nl80211MeshSetupParams {}
 ///
 /// Enumeration from nl80211/nl80211.h:3427
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TxqAttr {
     TxqAttrInvalid = 0,
     TxqAttrAc = 1,
@@ -722,6 +978,7 @@ impl NlAttrType for Nl80211TxqAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:3440
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Ac {
     AcVo = 0,
     AcVi = 1,
@@ -735,6 +992,7 @@ impl NlAttrType for Nl80211Ac {}
 ///
 /// Enumeration from nl80211/nl80211.h:3464
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ChannelType {
     ChanNoHt = 0,
     ChanHt20 = 1,
@@ -748,6 +1006,7 @@ impl NlAttrType for Nl80211ChannelType {}
 ///
 /// Enumeration from nl80211/nl80211.h:3490
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ChanWidth {
     ChanWidth20Noht = 0,
     ChanWidth20 = 1,
@@ -757,6 +1016,12 @@ pub enum Nl80211ChanWidth {
     ChanWidth160 = 5,
     ChanWidth5 = 6,
     ChanWidth10 = 7,
+    ChanWidth1 = 8,
+    ChanWidth2 = 9,
+    ChanWidth4 = 10,
+    ChanWidth8 = 11,
+    ChanWidth16 = 12,
+    ChanWidth320 = 13,
 }
 
 impl NlAttrType for Nl80211ChanWidth {}
@@ -765,6 +1030,7 @@ impl NlAttrType for Nl80211ChanWidth {}
 ///
 /// Enumeration from nl80211/nl80211.h:3510
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211BssScanWidth {
     BssChanWidth20 = 0,
     BssChanWidth10 = 1,
@@ -777,6 +1043,7 @@ impl NlAttrType for Nl80211BssScanWidth {}
 ///
 /// Enumeration from nl80211/nl80211.h:3565
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Bss {
     BssInvalid = 0,
     BssBssid = 1,
@@ -797,6 +1064,12 @@ pub enum Nl80211Bss {
     BssPad = 16,
     BssParentTsf = 17,
     BssParentBssid = 18,
+    BssChainSignal = 19,
+    BssFrequencyOffset = 20,
+    BssMloLinkId = 21,
+    BssMldAddr = 22,
+    BssUseFor = 23,
+    BssCannotUseReasons = 24,
 }
 
 impl NlAttrType for Nl80211Bss {}
@@ -805,6 +1078,7 @@ impl NlAttrType for Nl80211Bss {}
 ///
 /// Enumeration from nl80211/nl80211.h:3603
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211BssStatus {
     BssStatusAuthenticated = 0,
     BssStatusAssociated = 1,
@@ -817,15 +1091,19 @@ impl NlAttrType for Nl80211BssStatus {}
 ///
 /// Enumeration from nl80211/nl80211.h:3623
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211AuthType {
     AuthtypeOpenSystem = 0,
     AuthtypeSharedKey = 1,
     AuthtypeFt = 2,
     AuthtypeNetworkEap = 3,
     AuthtypeSae = 4,
-    AuthtypeNum = 5,
-    AuthtypeMax = 4,
-    AuthtypeAutomatic = 5,
+    AuthtypeFilsSk = 5,
+    AuthtypeFilsSkPfs = 6,
+    AuthtypeFilsPk = 7,
+    AuthtypeNum = 8,
+    AuthtypeMax = 7,
+    AuthtypeAutomatic = 8,
 }
 
 impl NlAttrType for Nl80211AuthType {}
@@ -834,6 +1112,7 @@ impl NlAttrType for Nl80211AuthType {}
 ///
 /// Enumeration from nl80211/nl80211.h:3643
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211KeyType {
     KeytypeGroup = 0,
     KeytypePairwise = 1,
@@ -846,6 +1125,7 @@ impl NlAttrType for Nl80211KeyType {}
 ///
 /// Enumeration from nl80211/nl80211.h:3656
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Mfp {
     MfpNo = 0,
     MfpRequired = 1,
@@ -857,6 +1137,7 @@ impl NlAttrType for Nl80211Mfp {}
 ///
 /// Enumeration from nl80211/nl80211.h:3661
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211WpaVersions {
     WpaVersion1 = 1 << 0,
     WpaVersion2 = 1 << 1,
@@ -868,6 +1149,7 @@ impl NlAttrType for Nl80211WpaVersions {}
 ///
 /// Enumeration from nl80211/nl80211.h:3675
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211KeyDefaultTypes {
     KeyDefaultTypeInvalid = 0,
     KeyDefaultTypeUnicast = 1,
@@ -880,6 +1162,7 @@ impl NlAttrType for Nl80211KeyDefaultTypes {}
 ///
 /// Enumeration from nl80211/nl80211.h:3705
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211KeyAttributes {
     KeyInvalid = 0,
     KeyData = 1,
@@ -898,6 +1181,7 @@ impl NlAttrType for Nl80211KeyAttributes {}
 ///
 /// Enumeration from nl80211/nl80211.h:3736
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TxRateAttributes {
     TxrateInvalid = 0,
     TxrateLegacy = 1,
@@ -912,6 +1196,7 @@ impl NlAttrType for Nl80211TxRateAttributes {}
 ///
 /// Enumeration from nl80211/nl80211.h:3759
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TxrateGi {
     TxrateDefaultGi = 0,
     TxrateForceSgi = 1,
@@ -924,10 +1209,14 @@ impl NlAttrType for Nl80211TxrateGi {}
 ///
 /// Enumeration from nl80211/nl80211.h:3773
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211Bandc {
     Band2ghz = 0,
     Band5ghz = 1,
     Band60ghz = 2,
+    Band6ghz = 3,
+    BandS1ghz = 4,
+    BandLc = 5,
 }
 
 impl NlAttrType for Nl80211Bandc {}
@@ -936,6 +1225,7 @@ impl NlAttrType for Nl80211Bandc {}
 ///
 /// Enumeration from nl80211/nl80211.h:3786
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211PsState {
     PsDisabled = 0,
     PsEnabled = 1,
@@ -947,6 +1237,7 @@ impl NlAttrType for Nl80211PsState {}
 ///
 /// Enumeration from nl80211/nl80211.h:3819
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211AttrCqm {
     AttrCqmInvalid = 0,
     AttrCqmRssiThold = 1,
@@ -965,6 +1256,7 @@ impl NlAttrType for Nl80211AttrCqm {}
 ///
 /// Enumeration from nl80211/nl80211.h:3843
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211CqmRssiThresholdEvent {
     CqmRssiThresholdEventLow = 0,
     CqmRssiThresholdEventHigh = 1,
@@ -977,6 +1269,7 @@ impl NlAttrType for Nl80211CqmRssiThresholdEvent {}
 ///
 /// Enumeration from nl80211/nl80211.h:3856
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TxPowerSetting {
     TxPowerAutomatic = 0,
     TxPowerLimited = 1,
@@ -989,6 +1282,7 @@ impl NlAttrType for Nl80211TxPowerSetting {}
 ///
 /// Enumeration from nl80211/nl80211.h:3883
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211PacketPatternAttr {
     PktpatInvalid = 0,
     PktpatMask = 1,
@@ -1002,6 +1296,7 @@ impl NlAttrType for Nl80211PacketPatternAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:4011
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211WowlanTriggers {
     WowlanTrigInvalid = 0,
     WowlanTrigAny = 1,
@@ -1023,6 +1318,7 @@ pub enum Nl80211WowlanTriggers {
     WowlanTrigWakeupTcpNomoretokens = 17,
     WowlanTrigNetDetect = 18,
     WowlanTrigNetDetectResults = 19,
+    WowlanTrigUnprotectedDeauthDisassoc = 20,
 }
 
 impl NlAttrType for Nl80211WowlanTriggers {}
@@ -1031,6 +1327,7 @@ impl NlAttrType for Nl80211WowlanTriggers {}
 ///
 /// Enumeration from nl80211/nl80211.h:4129
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211WowlanTcpAttrs {
     WowlanTcpInvalid = 0,
     WowlanTcpSrcIpv4 = 1,
@@ -1052,6 +1349,7 @@ impl NlAttrType for Nl80211WowlanTcpAttrs {}
 ///
 /// Enumeration from nl80211/nl80211.h:4174
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211AttrCoalesceRule {
     CoalesceRuleInvalid = 0,
     AttrCoalesceRuleDelay = 1,
@@ -1065,6 +1363,7 @@ impl NlAttrType for Nl80211AttrCoalesceRule {}
 ///
 /// Enumeration from nl80211/nl80211.h:4192
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211CoalesceCondition {
     CoalesceConditionMatch = 0,
     CoalesceConditionNoMatch = 1,
@@ -1076,6 +1375,7 @@ impl NlAttrType for Nl80211CoalesceCondition {}
 ///
 /// Enumeration from nl80211/nl80211.h:4207
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211IfaceLimitAttrs {
     IfaceLimitUnspec = 0,
     IfaceLimitMax = 1,
@@ -1088,6 +1388,7 @@ impl NlAttrType for Nl80211IfaceLimitAttrs {}
 ///
 /// Enumeration from nl80211/nl80211.h:4263
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211IfCombinationAttrs {
     IfaceCombUnspec = 0,
     IfaceCombLimits = 1,
@@ -1104,6 +1405,7 @@ impl NlAttrType for Nl80211IfCombinationAttrs {}
 ///
 /// Enumeration from nl80211/nl80211.h:4296
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211PlinkState {
     PlinkListen = 0,
     PlinkOpnSnt = 1,
@@ -1120,6 +1422,7 @@ impl NlAttrType for Nl80211PlinkState {}
 ///
 /// Enumeration from nl80211/nl80211.h:4318
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum PlinkActions {
     PlinkActionNoAction = 0,
     PlinkActionOpen = 1,
@@ -1132,6 +1435,7 @@ impl NlAttrType for PlinkActions {}
 ///
 /// Enumeration from nl80211/nl80211.h:4340
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RekeyData {
     RekeyDataInvalid = 0,
     RekeyDataKek = 1,
@@ -1145,6 +1449,7 @@ impl NlAttrType for Nl80211RekeyData {}
 ///
 /// Enumeration from nl80211/nl80211.h:4360
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211HiddenSsid {
     HiddenSsidNotInUse = 0,
     HiddenSsidZeroLen = 1,
@@ -1157,6 +1462,7 @@ impl NlAttrType for Nl80211HiddenSsid {}
 ///
 /// Enumeration from nl80211/nl80211.h:4376
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211StaWmeAttr {
     StaWmeInvalid = 0,
     StaWmeUapsdQueues = 1,
@@ -1169,6 +1475,7 @@ impl NlAttrType for Nl80211StaWmeAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:4398
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211PmksaCandidateAttr {
     PmksaCandidateInvalid = 0,
     PmksaCandidateIndex = 1,
@@ -1182,6 +1489,7 @@ impl NlAttrType for Nl80211PmksaCandidateAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:4417
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TdlsOperation {
     TdlsDiscoveryReq = 0,
     TdlsSetup = 1,
@@ -1196,6 +1504,7 @@ impl NlAttrType for Nl80211TdlsOperation {}
 ///
 /// Enumeration from nl80211/nl80211.h:4526
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211FeatureFlags {
     FeatureSkTxStatus = 1 << 0,
     FeatureHtIbss = 1 << 1,
@@ -1236,6 +1545,7 @@ impl NlAttrType for Nl80211FeatureFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:4595
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ExtFeatureIndex {
     ExtFeatureVhtIbss = 0,
     ExtFeatureRrm = 1,
@@ -1246,6 +1556,66 @@ pub enum Nl80211ExtFeatureIndex {
     ExtFeatureBeaconRateLegacy = 6,
     ExtFeatureBeaconRateHt = 7,
     ExtFeatureBeaconRateVht = 8,
+    ExtFeatureFilsSta = 9,
+    ExtFeatureMgmtTxRandomTa = 10,
+    ExtFeatureMgmtTxRandomTaConnected = 11,
+    ExtFeatureSchedScanRelativeRssi = 12,
+    ExtFeatureCqmRssiList = 13,
+    ExtFeatureFilsSkOffload = 14,
+    ExtFeature4wayHandshakeStaPsk = 15,
+    ExtFeature4wayHandshakeSta1x = 16,
+    ExtFeatureFilsMaxChannelTime = 17,
+    ExtFeatureAcceptBcastProbeResp = 18,
+    ExtFeatureOceProbeReqHighTxRate = 19,
+    ExtFeatureOceProbeReqDeferralSuppression = 20,
+    ExtFeatureMfpOptional = 21,
+    ExtFeatureLowSpanScan = 22,
+    ExtFeatureLowPowerScan = 23,
+    ExtFeatureHighAccuracyScan = 24,
+    ExtFeatureDfsOffload = 25,
+    ExtFeatureControlPortOverNl80211 = 26,
+    ExtFeatureAckSignalSupport = 27,
+    ExtFeatureTxqs = 28,
+    ExtFeatureScanRandomSn = 29,
+    ExtFeatureScanMinPreqContent = 30,
+    ExtFeatureCanReplacePtk0 = 31,
+    ExtFeatureEnableFtmResponder = 32,
+    ExtFeatureAirtimeFairness = 33,
+    ExtFeatureApPmksaCaching = 34,
+    ExtFeatureSchedScanBandSpecificRssiThold = 35,
+    ExtFeatureExtKeyId = 36,
+    ExtFeatureStaTxPwr = 37,
+    ExtFeatureSaeOffload = 38,
+    ExtFeatureVlanOffload = 39,
+    ExtFeatureAql = 40,
+    ExtFeatureBeaconProtection = 41,
+    ExtFeatureControlPortNoPreauth = 42,
+    ExtFeatureProtectedTwt = 43,
+    ExtFeatureDelIbssSta = 44,
+    ExtFeatureMulticastRegistrations = 45,
+    ExtFeatureBeaconProtectionClient = 46,
+    ExtFeatureScanFreqKhz = 47,
+    ExtFeatureControlPortOverNl80211TxStatus = 48,
+    ExtFeatureOperatingChannelValidation = 49,
+    ExtFeature4wayHandshakeApPsk = 50,
+    ExtFeatureSaeOffloadAp = 51,
+    ExtFeatureFilsDiscovery = 52,
+    ExtFeatureUnsolBcastProbeResp = 53,
+    ExtFeatureBeaconRateHe = 54,
+    ExtFeatureSecureLtf = 55,
+    ExtFeatureSecureRtt = 56,
+    ExtFeatureProtRangeNegoAndMeasure = 57,
+    ExtFeatureBssColor = 58,
+    ExtFeatureFilsCryptoOffload = 59,
+    ExtFeatureRadarBackground = 60,
+    ExtFeaturePoweredAddrChange = 61,
+    ExtFeaturePunct = 62,
+    ExtFeatureSecureNan = 63,
+    ExtFeatureAuthAndDeauthRandomTa = 64,
+    ExtFeatureOweOffload = 65,
+    ExtFeatureOweOffloadAp = 66,
+    ExtFeatureDfsConcurrent = 67,
+    ExtFeatureSppAmsduSupport = 68,
 }
 
 impl NlAttrType for Nl80211ExtFeatureIndex {}
@@ -1254,6 +1624,7 @@ impl NlAttrType for Nl80211ExtFeatureIndex {}
 ///
 /// Enumeration from nl80211/nl80211.h:4625
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ProbeRespOffloadSupportAttr {
     ProbeRespOffloadSupportWps = 1 << 0,
     ProbeRespOffloadSupportWps2 = 1 << 1,
@@ -1267,6 +1638,7 @@ impl NlAttrType for Nl80211ProbeRespOffloadSupportAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:4638
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ConnectFailedReason {
     ConnFailMaxClients = 0,
     ConnFailBlockedClient = 1,
@@ -1278,6 +1650,7 @@ impl NlAttrType for Nl80211ConnectFailedReason {}
 ///
 /// Enumeration from nl80211/nl80211.h:4667
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ScanFlags {
     ScanFlagLowPriority = 1 << 0,
     ScanFlagFlush = 1 << 1,
@@ -1291,6 +1664,7 @@ impl NlAttrType for Nl80211ScanFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:4687
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211AclPolicy {
     AclPolicyAcceptUnlessListed = 0,
     AclPolicyDenyUnlessListed = 1,
@@ -1302,6 +1676,7 @@ impl NlAttrType for Nl80211AclPolicy {}
 ///
 /// Enumeration from nl80211/nl80211.h:4702
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211SmpsMode {
     SmpsOff = 0,
     SmpsStatic = 1,
@@ -1314,6 +1689,7 @@ impl NlAttrType for Nl80211SmpsMode {}
 ///
 /// Enumeration from nl80211/nl80211.h:4726
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RadarEvent {
     RadarDetected = 0,
     RadarCacFinished = 1,
@@ -1327,6 +1703,7 @@ impl NlAttrType for Nl80211RadarEvent {}
 ///
 /// Enumeration from nl80211/nl80211.h:4744
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211DfsState {
     DfsUsable = 0,
     DfsUnavailable = 1,
@@ -1339,6 +1716,7 @@ impl NlAttrType for Nl80211DfsState {}
 ///
 /// Enumeration from nl80211/nl80211.h:4758
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211ProtocolFeatures {
     ProtocolFeatureSplitWiphyDump = 1 << 0,
 }
@@ -1349,6 +1727,7 @@ impl NlAttrType for Nl80211ProtocolFeatures {}
 ///
 /// Enumeration from nl80211/nl80211.h:4771
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211CritProtoId {
     CritProtoUnspec = 0,
     CritProtoDhcp = 1,
@@ -1362,6 +1741,7 @@ impl NlAttrType for Nl80211CritProtoId {}
 ///
 /// Enumeration from nl80211/nl80211.h:4790
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211RxmgmtFlags {
     RxmgmtFlagAnswered = 1 << 0,
 }
@@ -1372,6 +1752,7 @@ impl NlAttrType for Nl80211RxmgmtFlags {}
 ///
 /// Enumeration from nl80211/nl80211.h:4824
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211TdlsPeerCapability {
     TdlsPeerHt = 1 << 0,
     TdlsPeerVht = 1 << 1,
@@ -1384,6 +1765,7 @@ impl NlAttrType for Nl80211TdlsPeerCapability {}
 ///
 /// Enumeration from nl80211/nl80211.h:4843
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211SchedScanPlan {
     SchedScanPlanInvalid = 0,
     SchedScanPlanInterval = 1,
@@ -1396,6 +1778,7 @@ impl NlAttrType for Nl80211SchedScanPlan {}
 ///
 /// Enumeration from nl80211/nl80211.h:4887
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211BssSelectAttr {
     BssSelectAttrInvalid = 0,
     BssSelectAttrRssi = 1,
@@ -1409,6 +1792,7 @@ impl NlAttrType for Nl80211BssSelectAttr {}
 ///
 /// Enumeration from nl80211/nl80211.h:4907
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanDualBandConf {
     NanBandDefault = 1 << 0,
     NanBand2ghz = 1 << 1,
@@ -1421,6 +1805,7 @@ impl NlAttrType for Nl80211NanDualBandConf {}
 ///
 /// Enumeration from nl80211/nl80211.h:4922
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanFunctionType {
     NanFuncPublish = 0,
     NanFuncSubscribe = 1,
@@ -1435,6 +1820,7 @@ impl NlAttrType for Nl80211NanFunctionType {}
 ///
 /// Enumeration from nl80211/nl80211.h:4940
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanPublishType {
     NanSolicitedPublish = 1 << 0,
     NanUnsolicitedPublish = 1 << 1,
@@ -1446,6 +1832,7 @@ impl NlAttrType for Nl80211NanPublishType {}
 ///
 /// Enumeration from nl80211/nl80211.h:4954
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanFuncTermReason {
     NanFuncTermReasonUserRequest = 0,
     NanFuncTermReasonTtlExpired = 1,
@@ -1458,6 +1845,7 @@ impl NlAttrType for Nl80211NanFuncTermReason {}
 ///
 /// Enumeration from nl80211/nl80211.h:5006
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanFuncAttributes {
     NanFuncInvalid = 0,
     NanFuncType = 1,
@@ -1484,6 +1872,7 @@ impl NlAttrType for Nl80211NanFuncAttributes {}
 ///
 /// Enumeration from nl80211/nl80211.h:5045
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanSrfAttributes {
     NanSrfInvalid = 0,
     NanSrfInclude = 1,
@@ -1498,6 +1887,7 @@ impl NlAttrType for Nl80211NanSrfAttributes {}
 ///
 /// Enumeration from nl80211/nl80211.h:5070
 #[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
 pub enum Nl80211NanMatchAttributes {
     NanMatchInvalid = 0,
     NanMatchFuncLocal = 1,
@@ -1505,3 +1895,57 @@ pub enum Nl80211NanMatchAttributes {
 }
 
 impl NlAttrType for Nl80211NanMatchAttributes {}
+
+#[cfg(test)]
+mod test_attr {
+    use super::*;
+    use crate::cmd::Nl80211Cmd;
+
+    #[test]
+    fn test_unknown_constants() {
+        let attr = Nl80211Attr::from(1000u16);
+        assert_eq!(attr, Nl80211Attr::UnrecognizedConst(1000));
+        assert!(attr.is_unrecognized());
+        assert_eq!(u16::from(attr), 1000);
+
+        assert_eq!(Nl80211Attr::from(3u16), Nl80211Attr::AttrIfindex);
+        assert_eq!(u16::from(Nl80211Bss::BssTsf), 3);
+        assert_eq!(u8::from(Nl80211Cmd::from(250u8)), 250);
+    }
+
+    #[test]
+    fn test_recent_constants() {
+        assert_eq!(Nl80211Attr::from(312u16), Nl80211Attr::AttrMloLinks);
+        assert_eq!(Nl80211Attr::from(333u16), Nl80211Attr::AttrS1gShortBeacon);
+        assert_eq!(
+            Nl80211Cmd::from(121u8),
+            Nl80211Cmd::CmdSetMulticastToUnicast
+        );
+        assert_eq!(Nl80211Cmd::from(148u8), Nl80211Cmd::CmdAddLink);
+        assert_eq!(Nl80211Cmd::from(140u8), Nl80211Cmd::CmdSetSarSpecs);
+        assert_eq!(Nl80211Cmd::from(157u8), Nl80211Cmd::CmdEpcsCfg);
+        assert_eq!(u16::from(Nl80211StaInfo::StaInfoConnectedToAs), 43);
+        assert_eq!(u16::from(Nl80211Bss::BssMldAddr), 22);
+        assert_eq!(
+            Nl80211FrequencyAttr::from(27u16),
+            Nl80211FrequencyAttr::FrequencyAttrNoEht
+        );
+        assert_eq!(
+            u16::from(Nl80211ExtFeatureIndex::ExtFeatureBeaconProtection),
+            41
+        );
+    }
+
+    #[test]
+    fn test_nested_attrs() {
+        let payload = [6, 0, 1, 0, 7, 7, 0, 0, 4, 0, 2, 0];
+        let attrs: Vec<_> = NestedAttrs::new(&payload).map(Result::unwrap).collect();
+        assert_eq!(attrs, vec![(1, &[7, 7][..]), (2, &[][..])]);
+
+        let truncated = [6, 0, 1, 0, 7, 7, 0, 0, 9, 0, 2, 0];
+        let mut attrs = NestedAttrs::new(&truncated);
+        assert!(attrs.next().unwrap().is_ok());
+        assert!(attrs.next().unwrap().is_err());
+        assert!(attrs.next().is_none());
+    }
+}
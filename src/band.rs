@@ -0,0 +1,57 @@
+use crate::attr::Nl80211Bandc;
+
+impl Nl80211Bandc {
+    /// Band of a channel with the given center frequency (MHz)
+    pub fn from_frequency(frequency: u32) -> Option<Self> {
+        match frequency {
+            700..=1000 => Some(Self::BandS1ghz),
+            2400..=2500 => Some(Self::Band2ghz),
+            4900..=5924 => Some(Self::Band5ghz),
+            5925..=7125 => Some(Self::Band6ghz),
+            58320..=70200 => Some(Self::Band60ghz),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_band {
+    use super::*;
+
+    #[test]
+    fn test_from_frequency() {
+        assert_eq!(
+            Nl80211Bandc::from_frequency(2412),
+            Some(Nl80211Bandc::Band2ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(2484),
+            Some(Nl80211Bandc::Band2ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(5180),
+            Some(Nl80211Bandc::Band5ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(5865),
+            Some(Nl80211Bandc::Band5ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(5935),
+            Some(Nl80211Bandc::Band6ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(5955),
+            Some(Nl80211Bandc::Band6ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(60480),
+            Some(Nl80211Bandc::Band60ghz)
+        );
+        assert_eq!(
+            Nl80211Bandc::from_frequency(916),
+            Some(Nl80211Bandc::BandS1ghz)
+        );
+        assert_eq!(Nl80211Bandc::from_frequency(3600), None);
+    }
+}
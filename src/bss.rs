@@ -1,8 +1,10 @@
-use crate::attr::{Attrs, Nl80211Attr, Nl80211Bss};
+use crate::attr::{exact_payload, Attrs, NestedAttrs, Nl80211Attr, Nl80211Bandc, Nl80211Bss};
+use crate::power::Mbm;
 
-use neli::attr::Attribute;
 use neli::err::DeError;
 
+use std::time::Duration;
+
 /// A struct representing a BSS (Basic Service Set)
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Bss {
@@ -10,47 +12,237 @@ pub struct Bss {
     pub bssid: Option<Vec<u8>>,
     /// Frequency in MHz
     pub frequency: Option<u32>,
-    /// Beacon interval of the (I)BSS
+    /// Beacon interval of the (I)BSS in TU (1024 µs)
     pub beacon_interval: Option<u16>,
+    /// Timing synchronization function (TSF) timer of the BSS in µs, from the last received
+    /// beacon or probe response
+    pub tsf: Option<u64>,
+    /// TSF timer in µs from the last received beacon, if a beacon was received
+    pub beacon_tsf: Option<u64>,
     /// Age of this BSS entry in ms
     pub seen_ms_ago: Option<u32>,
     /// Status, if this BSS is "used"
     pub status: Option<u32>,
     /// Signal strength of probe response/beacon in mBm (100 * dBm)
     pub signal: Option<i32>,
+    /// Signal strength of probe response/beacon in unspecified units, scaled to 0..100, for
+    /// drivers which can't report dBm
+    pub signal_unspec: Option<u8>,
     /// binary attribute containing the raw information elements from the probe response/beacon.
     pub information_elements: Option<Vec<u8>>,
 }
 
-impl TryFrom<Attrs<'_, Nl80211Attr>> for Bss {
+/// Signal strength of a BSS, in the unit reported by the driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Signal strength in dBm, with the 0.01 dB precision reported by the driver
+    Dbm(Mbm),
+    /// Signal quality in unspecified units, from 0 (worst) to 100 (best)
+    Unspec(u8),
+}
+
+impl Bss {
+    /// Signal strength of the last probe response/beacon, whichever way the driver reports it
+    pub fn signal(&self) -> Option<Signal> {
+        match (self.signal, self.signal_unspec) {
+            (Some(mbm), _) => Some(Signal::Dbm(Mbm(mbm))),
+            (None, Some(unspec)) => Some(Signal::Unspec(unspec)),
+            (None, None) => None,
+        }
+    }
+
+    /// SSID advertised in the information elements
+    pub fn ssid(&self) -> Option<&[u8]> {
+        BssRef::from(self).ssid()
+    }
+
+    /// Iterate over the information elements
+    pub fn elements(&self) -> Elements<'_> {
+        BssRef::from(self).elements()
+    }
+
+    /// Whether the BSS hides its SSID, by advertising an empty SSID or one made of NUL bytes
+    pub fn is_hidden_ssid(&self) -> bool {
+        self.ssid()
+            .is_some_and(|ssid| ssid.iter().all(|byte| *byte == 0))
+    }
+
+    /// Beacon interval in milliseconds
+    pub fn beacon_interval_ms(&self) -> Option<f64> {
+        self.beacon_interval.map(|tu| f64::from(tu) * 1.024)
+    }
+
+    /// Beacon interval as a [`Duration`]
+    pub fn beacon_interval_duration(&self) -> Option<Duration> {
+        self.beacon_interval
+            .map(|tu| Duration::from_micros(u64::from(tu) * 1024))
+    }
+
+    /// TSF timer as a [`Duration`], which is usually the time since the AP started beaconing
+    pub fn tsf_duration(&self) -> Option<Duration> {
+        self.tsf.map(Duration::from_micros)
+    }
+
+    /// Band the BSS operates on
+    pub fn band(&self) -> Option<Nl80211Bandc> {
+        self.frequency.and_then(Nl80211Bandc::from_frequency)
+    }
+}
+
+const SSID_ELEMENT_ID: u8 = 0;
+
+/// Find the first information element with the given id and return its data
+pub(crate) fn find_element(ies: &[u8], element_id: u8) -> Option<&[u8]> {
+    Elements::new(ies)
+        .find(|(id, _)| *id == element_id)
+        .map(|(_, data)| data)
+}
+
+/// Iterator over information elements, yielding the id and data of each element
+///
+/// The iteration stops at the first truncated element.
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    ies: &'a [u8],
+}
+
+impl<'a> Elements<'a> {
+    /// Iterate over raw information elements, such as [`Bss::information_elements`]
+    pub fn new(ies: &'a [u8]) -> Self {
+        Self { ies }
+    }
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let [id, len, rest @ ..] = self.ies else {
+            return None;
+        };
+        let Some(data) = rest.get(..usize::from(*len)) else {
+            self.ies = &[];
+            return None;
+        };
+        self.ies = &rest[data.len()..];
+        Some((*id, data))
+    }
+}
+
+/// A BSS borrowing its BSSID and information elements from the message it was parsed from,
+/// see [`Socket::for_each_bss`](crate::Socket::for_each_bss)
+///
+/// Scan dumps often hold hundreds of BSSes with hundreds of bytes of information elements
+/// each, this avoids copying them when only a few are kept.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BssRef<'a> {
+    /// BSSID
+    pub bssid: Option<&'a [u8]>,
+    /// Frequency in MHz
+    pub frequency: Option<u32>,
+    /// Beacon interval of the (I)BSS in TU (1024 µs)
+    pub beacon_interval: Option<u16>,
+    /// TSF timer of the BSS in µs, see [`Bss::tsf`]
+    pub tsf: Option<u64>,
+    /// TSF timer in µs from the last received beacon
+    pub beacon_tsf: Option<u64>,
+    /// Age of this BSS entry in ms
+    pub seen_ms_ago: Option<u32>,
+    /// Status, if this BSS is "used"
+    pub status: Option<u32>,
+    /// Signal strength of probe response/beacon in mBm (100 * dBm)
+    pub signal: Option<i32>,
+    /// Signal strength of probe response/beacon in unspecified units, scaled to 0..100
+    pub signal_unspec: Option<u8>,
+    /// Raw information elements from the probe response/beacon
+    pub information_elements: Option<&'a [u8]>,
+}
+
+impl<'a> BssRef<'a> {
+    /// SSID advertised in the information elements
+    pub fn ssid(&self) -> Option<&'a [u8]> {
+        find_element(self.information_elements?, SSID_ELEMENT_ID)
+    }
+
+    /// Iterate over the information elements
+    pub fn elements(&self) -> Elements<'a> {
+        Elements::new(self.information_elements.unwrap_or_default())
+    }
+}
+
+impl From<BssRef<'_>> for Bss {
+    fn from(bss: BssRef<'_>) -> Self {
+        Self {
+            bssid: bss.bssid.map(<[u8]>::to_vec),
+            frequency: bss.frequency,
+            beacon_interval: bss.beacon_interval,
+            tsf: bss.tsf,
+            beacon_tsf: bss.beacon_tsf,
+            seen_ms_ago: bss.seen_ms_ago,
+            status: bss.status,
+            signal: bss.signal,
+            signal_unspec: bss.signal_unspec,
+            information_elements: bss.information_elements.map(<[u8]>::to_vec),
+        }
+    }
+}
+
+impl<'a> From<&'a Bss> for BssRef<'a> {
+    fn from(bss: &'a Bss) -> Self {
+        Self {
+            bssid: bss.bssid.as_deref(),
+            frequency: bss.frequency,
+            beacon_interval: bss.beacon_interval,
+            tsf: bss.tsf,
+            beacon_tsf: bss.beacon_tsf,
+            seen_ms_ago: bss.seen_ms_ago,
+            status: bss.status,
+            signal: bss.signal,
+            signal_unspec: bss.signal_unspec,
+            information_elements: bss.information_elements.as_deref(),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Attrs<'_, Nl80211Attr>> for BssRef<'a> {
     type Error = DeError;
 
-    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+    fn try_from(attrs: &'a Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
         let mut res = Self::default();
         if let Some(bss) = attrs.get_attribute(Nl80211Attr::AttrBss) {
-            let attrs = bss.get_attr_handle::<Nl80211Bss>()?;
-            for attr in attrs.iter() {
-                match attr.nla_type.nla_type {
+            for attr in NestedAttrs::new(bss.nla_payload.as_ref()) {
+                let (attr_type, payload) = attr?;
+                match Nl80211Bss::from(attr_type) {
                     Nl80211Bss::BssBssid => {
-                        res.bssid = Some(attr.get_payload_as_with_len()?);
+                        exact_payload::<6>(payload)?;
+                        res.bssid = Some(payload);
                     }
                     Nl80211Bss::BssFrequency => {
-                        res.frequency = Some(attr.get_payload_as()?);
+                        res.frequency = Some(u32::from_ne_bytes(exact_payload(payload)?));
                     }
                     Nl80211Bss::BssBeaconInterval => {
-                        res.beacon_interval = Some(attr.get_payload_as()?);
+                        res.beacon_interval = Some(u16::from_ne_bytes(exact_payload(payload)?));
+                    }
+                    Nl80211Bss::BssTsf => {
+                        res.tsf = Some(u64::from_ne_bytes(exact_payload(payload)?));
+                    }
+                    Nl80211Bss::BssBeaconTsf => {
+                        res.beacon_tsf = Some(u64::from_ne_bytes(exact_payload(payload)?));
                     }
                     Nl80211Bss::BssSeenMsAgo => {
-                        res.seen_ms_ago = Some(attr.get_payload_as()?);
+                        res.seen_ms_ago = Some(u32::from_ne_bytes(exact_payload(payload)?));
                     }
                     Nl80211Bss::BssStatus => {
-                        res.status = Some(attr.get_payload_as()?);
+                        res.status = Some(u32::from_ne_bytes(exact_payload(payload)?));
                     }
                     Nl80211Bss::BssSignalMbm => {
-                        res.signal = Some(attr.get_payload_as()?);
+                        res.signal = Some(i32::from_ne_bytes(exact_payload(payload)?));
+                    }
+                    Nl80211Bss::BssSignalUnspec => {
+                        res.signal_unspec = Some(u8::from_ne_bytes(exact_payload(payload)?));
                     }
                     Nl80211Bss::BssInformationElements => {
-                        res.information_elements = Some(attr.get_payload_as_with_len()?);
+                        res.information_elements = Some(payload);
                     }
                     _ => (),
                 }
@@ -60,6 +252,14 @@ impl TryFrom<Attrs<'_, Nl80211Attr>> for Bss {
     }
 }
 
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Bss {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        BssRef::try_from(&attrs).map(Bss::from)
+    }
+}
+
 #[cfg(test)]
 mod test_bss {
     use super::*;
@@ -127,16 +327,23 @@ mod test_bss {
             ),
         ];
 
-        let bss: Bss = AttrHandle::new(handler.into_iter().collect())
-            .try_into()
-            .unwrap();
+        let attrs = AttrHandle::new(handler.into_iter().collect());
+        let bss_ref = BssRef::try_from(&attrs).unwrap();
+        assert_eq!(bss_ref.ssid(), Some(&b"SFR-1c28"[..]));
+        let owned = Bss::from(bss_ref);
+        assert_eq!(BssRef::from(&owned), bss_ref);
+        let bss: Bss = attrs.try_into().unwrap();
+        assert_eq!(owned, bss);
         let expected_bss = Bss {
             bssid: Some(vec![255, 255, 255, 255, 255, 255]),
             frequency: Some(u32::from_le_bytes([108, 9, 0, 0])),
             beacon_interval: Some(u16::from_le_bytes([100, 0])),
+            tsf: Some(u64::from_le_bytes([132, 12, 93, 163, 39, 0, 0, 0])),
+            beacon_tsf: Some(u64::from_le_bytes([187, 118, 116, 163, 39, 0, 0, 0])),
             seen_ms_ago: Some(u32::from_le_bytes([100, 0, 0, 0])),
             status: Some(u32::from_le_bytes([1, 0, 0, 0])),
             signal: Some(i32::from_le_bytes([76, 235, 255, 255])),
+            signal_unspec: None,
             information_elements: Some(vec![
                 0, 8, 83, 70, 82, 45, 49, 99, 50, 56, 1, 8, 130, 132, 139, 150, 36, 48, 72, 108, 3,
                 1, 1, 7, 6, 68, 69, 32, 1, 13, 20, 32, 1, 0, 35, 2, 16, 0, 42, 1, 0, 50, 4, 12, 18,
@@ -157,6 +364,66 @@ mod test_bss {
             ]),
         };
 
-        assert_eq!(bss, expected_bss)
+        assert_eq!(bss, expected_bss);
+        assert_eq!(bss.signal(), Some(Signal::Dbm(Mbm::from_dbm(-53))));
+        assert_eq!(bss.ssid(), Some(&b"SFR-1c28"[..]));
+        assert!(!bss.is_hidden_ssid());
+        assert_eq!(bss.beacon_interval_ms(), Some(102.4));
+        assert_eq!(
+            bss.beacon_interval_duration(),
+            Some(Duration::from_micros(102_400))
+        );
+        assert_eq!(
+            bss.tsf_duration(),
+            Some(Duration::from_micros(170_244_508_804))
+        );
+    }
+
+    #[test]
+    fn test_signal() {
+        let bss = Bss {
+            signal: Some(-7050),
+            signal_unspec: Some(70),
+            ..Default::default()
+        };
+        let signal = bss.signal();
+        assert_eq!(signal, Some(Signal::Dbm(Mbm(-7050))));
+        assert!(matches!(signal, Some(Signal::Dbm(mbm)) if mbm.dbm() == -70.5));
+
+        let bss = Bss {
+            signal_unspec: Some(70),
+            ..Default::default()
+        };
+        assert_eq!(bss.signal(), Some(Signal::Unspec(70)));
+        assert_eq!(Bss::default().signal(), None);
+    }
+
+    #[test]
+    fn test_hidden_ssid() {
+        let mut bss = Bss {
+            information_elements: Some(vec![0, 0, 1, 1, 130]),
+            ..Default::default()
+        };
+        assert!(bss.is_hidden_ssid());
+        bss.information_elements = Some(vec![0, 3, 0, 0, 0, 1, 1, 130]);
+        assert!(bss.is_hidden_ssid());
+        bss.information_elements = Some(vec![1, 1, 130]);
+        assert!(!bss.is_hidden_ssid());
+        bss.information_elements = Some(vec![0, 4, 0]);
+        assert!(!bss.is_hidden_ssid());
+    }
+
+    #[test]
+    fn test_elements() {
+        let ies = [0, 2, b'a', b'b', 1, 1, 130, 3, 5, 1];
+        let elements: Vec<_> = Elements::new(&ies).collect();
+        assert_eq!(elements, vec![(0, &b"ab"[..]), (1, &[130][..])]);
+    }
+
+    #[test]
+    fn test_invalid_bssid() {
+        let handler = vec![new_attr(AttrBss, vec![8, 0, 1, 0, 2, 0, 0, 0])];
+        let attrs = AttrHandle::new(handler.into_iter().collect());
+        assert!(BssRef::try_from(&attrs).is_err());
     }
 }
@@ -0,0 +1,216 @@
+use crate::bss::Bss;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A BSS of a [`BssTable`], with the times it was seen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BssEntry {
+    /// The freshest scan result of the BSS
+    pub bss: Bss,
+    /// When the BSS was first seen
+    pub first_seen: Instant,
+    /// When the BSS was last seen, i.e. when its last beacon or probe response was received
+    pub last_seen: Instant,
+}
+
+/// Scan results merged from successive dumps of [`Socket::get_bss_info`](crate::Socket::get_bss_info),
+/// with one entry per BSSID
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{BssTable, Socket};
+///
+/// let mut socket = Socket::connect()?;
+/// let mut table = BssTable::new();
+/// table.update(socket.get_bss_info(3)?);
+/// for entry in table.entries() {
+///     println!("{:?} {:?}", entry.bss.bssid, entry.bss.signal());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct BssTable {
+    entries: Vec<BssEntry>,
+    /// Position of each BSSID in `entries`
+    index: HashMap<[u8; 6], usize>,
+}
+
+impl BssTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge the results of a scan dump taken now
+    pub fn update(&mut self, results: Vec<Bss>) {
+        self.update_at(results, Instant::now())
+    }
+
+    /// Merge the results of a scan dump taken at `now`
+    ///
+    /// An entry is replaced when the new result is at least as fresh, according to its
+    /// [`seen_ms_ago`](Bss::seen_ms_ago), keeping the previous information elements if the new
+    /// result has none. Results without a valid BSSID are ignored.
+    pub fn update_at(&mut self, results: Vec<Bss>, now: Instant) {
+        for mut bss in results {
+            let bssid = match bss
+                .bssid
+                .as_deref()
+                .and_then(|b| <[u8; 6]>::try_from(b).ok())
+            {
+                Some(bssid) => bssid,
+                None => continue,
+            };
+            let age = Duration::from_millis(bss.seen_ms_ago.unwrap_or(0).into());
+            let seen = now.checked_sub(age).unwrap_or(now);
+            match self.index.get(&bssid) {
+                Some(&position) => {
+                    let entry = &mut self.entries[position];
+                    entry.first_seen = entry.first_seen.min(seen);
+                    if seen >= entry.last_seen {
+                        if bss.information_elements.is_none() {
+                            bss.information_elements = entry.bss.information_elements.take();
+                        }
+                        entry.bss = bss;
+                        entry.last_seen = seen;
+                    }
+                }
+                None => {
+                    self.index.insert(bssid, self.entries.len());
+                    self.entries.push(BssEntry {
+                        bss,
+                        first_seen: seen,
+                        last_seen: seen,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Remove the entries which weren't seen for longer than `max_age`
+    pub fn expire(&mut self, max_age: Duration) {
+        let now = Instant::now();
+        let len = self.entries.len();
+        self.entries
+            .retain(|entry| now.saturating_duration_since(entry.last_seen) <= max_age);
+        if self.entries.len() != len {
+            self.reindex();
+        }
+    }
+
+    /// Entries of the table, in the order their BSS were first merged
+    pub fn entries(&self) -> &[BssEntry] {
+        &self.entries
+    }
+
+    /// Entry of a BSSID
+    pub fn get(&self, bssid: &[u8]) -> Option<&BssEntry> {
+        let bssid = <[u8; 6]>::try_from(bssid).ok()?;
+        self.index
+            .get(&bssid)
+            .map(|&position| &self.entries[position])
+    }
+
+    /// Number of entries in the table
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the table has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all the entries
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    /// Rebuild the positions of the BSSIDs after entries were removed
+    fn reindex(&mut self) {
+        self.index = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(position, entry)| {
+                let bssid = <[u8; 6]>::try_from(entry.bss.bssid.as_deref()?).ok()?;
+                Some((bssid, position))
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test_bss_table {
+    use super::*;
+
+    fn bss(bssid: u8, signal: i32, seen_ms_ago: u32, ies: Option<Vec<u8>>) -> Bss {
+        Bss {
+            bssid: Some(vec![2, 0, 0, 0, 0, bssid]),
+            signal: Some(signal),
+            seen_ms_ago: Some(seen_ms_ago),
+            information_elements: ies,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let start = Instant::now();
+        let mut table = BssTable::new();
+        table.update_at(
+            vec![
+                bss(1, -6000, 0, Some(vec![0, 1, b'a'])),
+                bss(2, -7000, 500, None),
+                Bss::default(),
+            ],
+            start,
+        );
+        assert_eq!(table.len(), 2);
+
+        let later = start + Duration::from_secs(10);
+        table.update_at(
+            vec![
+                bss(1, -5000, 1000, None),
+                // Cached result from before the previous dump
+                bss(2, -9000, 20_000, None),
+                bss(3, -8000, 0, None),
+            ],
+            later,
+        );
+        assert_eq!(table.len(), 3);
+
+        let first = table.get(&[2, 0, 0, 0, 0, 1]).unwrap();
+        assert_eq!(first.bss.signal, Some(-5000));
+        assert_eq!(first.bss.information_elements, Some(vec![0, 1, b'a']));
+        assert_eq!(first.first_seen, start);
+        assert_eq!(first.last_seen, later - Duration::from_secs(1));
+
+        let second = table.get(&[2, 0, 0, 0, 0, 2]).unwrap();
+        assert_eq!(second.bss.signal, Some(-7000));
+        assert_eq!(second.last_seen, start - Duration::from_millis(500));
+        assert_eq!(second.first_seen, later - Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut table = BssTable::new();
+        let old = Instant::now() - Duration::from_secs(60);
+        table.update_at(vec![bss(1, -6000, 0, None)], old);
+        table.update(vec![bss(2, -7000, 0, None), bss(3, -7000, 0, None)]);
+        table.expire(Duration::from_secs(30));
+        assert_eq!(table.len(), 2);
+        assert!(table.get(&[2, 0, 0, 0, 0, 1]).is_none());
+        assert_eq!(
+            table.get(&[2, 0, 0, 0, 0, 3]).unwrap().bss.bssid,
+            Some(vec![2, 0, 0, 0, 0, 3])
+        );
+
+        table.update(vec![bss(3, -5000, 0, None)]);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.entries()[1].bss.signal, Some(-5000));
+    }
+}
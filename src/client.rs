@@ -0,0 +1,424 @@
+use crate::snapshot::take_snapshot;
+use crate::wait::wait_for_interface;
+use crate::ApConfig;
+use crate::Bss;
+use crate::BssConfig;
+use crate::BssRef;
+use crate::CommandQueue;
+use crate::ConnectParams;
+use crate::Event;
+use crate::Interface;
+use crate::InterfaceMatch;
+use crate::Mbm;
+use crate::Nl80211Iftype;
+use crate::Nl80211MntrFlags;
+use crate::ScanInfo;
+use crate::ScanParams;
+use crate::SchedScanParams;
+use crate::Socket;
+use crate::SocketBuilder;
+use crate::Station;
+use crate::Survey;
+use crate::TxPower;
+use crate::TxqLimits;
+use crate::TxqParams;
+use crate::WifiSnapshot;
+use crate::Wiphy;
+
+use neli::consts::genl::{CtrlAttr, CtrlCmd};
+use neli::consts::nl::GenlId;
+use neli::err::NlError;
+use neli::genl::Genlmsghdr;
+
+use std::time::Duration;
+
+/// An nl80211 client using one socket for commands and another one for events
+///
+/// Notifications are only ever received on the event socket, so they can't get in the way of
+/// a pending command, and commands never consume notifications.
+///
+/// # Example
+///
+/// ```no_run
+/// # use neli_wifi::WifiClient;
+/// # use std::error::Error;
+///
+/// # fn main() -> Result<(), Box<dyn Error>>{
+///     let mut client = WifiClient::connect()?;
+///     client.subscribe("mlme")?;
+///     client.subscribe("scan")?;
+///     loop {
+///         println!("{:#?}", client.next_event()?);
+///         for interface in client.get_interfaces_info()? {
+///             println!("{:#?}", interface);
+///         }
+///     }
+/// # }
+///```
+pub struct WifiClient {
+    commands: Socket,
+    events: Socket,
+}
+
+impl WifiClient {
+    /// Create the command and event sockets
+    pub fn connect() -> Result<Self, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        Self::connect_with(SocketBuilder::new())
+    }
+
+    /// Create the command and event sockets, both with the given options
+    pub fn connect_with(
+        options: SocketBuilder,
+    ) -> Result<Self, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        Ok(Self {
+            commands: options.clone().connect()?,
+            events: options.connect()?,
+        })
+    }
+
+    /// The socket used to send commands, for the requests not wrapped by [`WifiClient`]
+    pub fn command_socket(&mut self) -> &mut Socket {
+        &mut self.commands
+    }
+
+    /// Move the command socket to a [`CommandQueue`], to share it between threads, and return
+    /// the event socket
+    pub fn into_command_queue(self, min_interval: Duration) -> (CommandQueue, Socket) {
+        (
+            CommandQueue::spawn(self.commands, min_interval),
+            self.events,
+        )
+    }
+
+    /// The socket events are received on
+    pub fn event_socket(&mut self) -> &mut Socket {
+        &mut self.events
+    }
+
+    /// Join an nl80211 multicast group on the event socket, see [`Socket::subscribe`]
+    pub fn subscribe(
+        &mut self,
+        group: &str,
+    ) -> Result<(), NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        self.events.subscribe(group)
+    }
+
+    /// Wait for the next event, see [`Socket::next_event`]
+    pub fn next_event(&mut self) -> Result<Event, NlError> {
+        self.events.next_event()
+    }
+
+    /// Receive management frames on the event socket, see [`Socket::register_frame`]
+    pub fn register_frame(
+        &mut self,
+        interface_index: i32,
+        frame_type: u16,
+        frame_match: &[u8],
+    ) -> Result<(), NlError> {
+        self.events
+            .register_frame(interface_index, frame_type, frame_match)
+    }
+
+    /// Receive action frames on the event socket, see [`Socket::register_action_frames`]
+    pub fn register_action_frames(
+        &mut self,
+        interface_index: i32,
+        category: u8,
+    ) -> Result<(), NlError> {
+        self.events
+            .register_action_frames(interface_index, category)
+    }
+
+    /// Receive beacons on the event socket, see [`Socket::register_beacons`]
+    pub fn register_beacons(&mut self, phy: u32) -> Result<(), NlError> {
+        self.events.register_beacons(phy)
+    }
+
+    /// Get information for all your wifi interfaces, see [`Socket::get_interfaces_info`]
+    pub fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, NlError> {
+        self.commands.get_interfaces_info()
+    }
+
+    /// Get access point information for a specific interface, see [`Socket::get_station_info`]
+    pub fn get_station_info(&mut self, interface_index: i32) -> Result<Station, NlError> {
+        self.commands.get_station_info(interface_index)
+    }
+
+    /// Get the scan results of a specific interface, see [`Socket::get_bss_info`]
+    pub fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
+        self.commands.get_bss_info(interface_index)
+    }
+
+    /// Call `f` with each scan result of a specific interface, see [`Socket::for_each_bss`]
+    pub fn for_each_bss<F>(&mut self, interface_index: i32, f: F) -> Result<(), NlError>
+    where
+        F: FnMut(BssRef<'_>),
+    {
+        self.commands.for_each_bss(interface_index, f)
+    }
+
+    /// Start a scan on an interface, see [`Socket::trigger_scan`]
+    pub fn trigger_scan(
+        &mut self,
+        interface_index: i32,
+        params: &ScanParams,
+    ) -> Result<(), NlError> {
+        self.commands.trigger_scan(interface_index, params)
+    }
+
+    /// Scan on an interface and wait for the scan to end, see [`Socket::scan`]
+    ///
+    /// The events of the event socket are left untouched, the receive timeout of the command
+    /// socket applies to the wait.
+    pub fn scan(&mut self, interface_index: i32, params: &ScanParams) -> Result<ScanInfo, NlError> {
+        self.commands.scan(interface_index, params)
+    }
+
+    /// Start a scheduled scan on an interface, see [`Socket::start_sched_scan`]
+    ///
+    /// With [`SchedScanParams::socket_owner`], the scheduled scan lives as long as the command
+    /// socket.
+    pub fn start_sched_scan(
+        &mut self,
+        interface_index: i32,
+        params: &SchedScanParams,
+    ) -> Result<(), NlError> {
+        self.commands.start_sched_scan(interface_index, params)
+    }
+
+    /// Stop the scheduled scan of an interface, see [`Socket::stop_sched_scan`]
+    pub fn stop_sched_scan(&mut self, interface_index: i32) -> Result<(), NlError> {
+        self.commands.stop_sched_scan(interface_index)
+    }
+
+    /// Wait until an interface matching `matcher` exists, e.g. after a driver reload or when a
+    /// USB adapter is plugged in, failing with [`std::io::ErrorKind::TimedOut`] after `timeout`
+    ///
+    /// The notifications are received on a temporary socket joining the "config" multicast
+    /// group, so the events of the event socket aren't consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use neli_wifi::{InterfaceMatch, WifiClient};
+    /// use std::time::Duration;
+    ///
+    /// let mut client = WifiClient::connect()?;
+    /// let interface =
+    ///     client.wait_for_interface(&InterfaceMatch::name("wlan0"), Some(Duration::from_secs(10)))?;
+    /// println!("{:?}", interface.index);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn wait_for_interface(
+        &mut self,
+        matcher: &InterfaceMatch,
+        timeout: Option<Duration>,
+    ) -> Result<Interface, NlError> {
+        wait_for_interface(&mut self.commands, matcher, timeout)
+    }
+
+    /// Get the interfaces with their station and scan results, and the survey of their wiphys,
+    /// in one go
+    ///
+    /// The interfaces are listed on the command socket, then each interface and wiphy is queried
+    /// from its own thread and socket, so scraping many radios takes about as long as scraping
+    /// one. With the `async` feature, `AsyncSocket::snapshot` does the same without threads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use neli_wifi::WifiClient;
+    ///
+    /// let mut client = WifiClient::connect()?;
+    /// for entry in client.snapshot()?.interfaces {
+    ///     match entry.station {
+    ///         Ok(station) => println!("{:?}: {:?} dBm", entry.interface.name, station.signal),
+    ///         Err(err) => println!("{:?}: {}", entry.interface.name, err),
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn snapshot(&mut self) -> Result<WifiSnapshot, NlError> {
+        take_snapshot(&mut self.commands)
+    }
+
+    /// Get information for all your wiphys, see [`Socket::get_wiphys`]
+    pub fn get_wiphys(&mut self) -> Result<Vec<Wiphy>, NlError> {
+        self.commands.get_wiphys()
+    }
+
+    /// Get the wiphy with the given name, see [`Socket::get_wiphy_by_name`]
+    pub fn get_wiphy_by_name(&mut self, name: &str) -> Result<Option<Wiphy>, NlError> {
+        self.commands.get_wiphy_by_name(name)
+    }
+
+    /// Get the channel survey of the wiphy of an interface, see [`Socket::get_survey`]
+    pub fn get_survey(&mut self, interface_index: i32) -> Result<Vec<Survey>, NlError> {
+        self.commands.get_survey(interface_index)
+    }
+
+    /// Get the channel survey of a wiphy, see [`Socket::get_survey_for_phy`]
+    pub fn get_survey_for_phy(&mut self, phy: u32) -> Result<Vec<Survey>, NlError> {
+        self.commands.get_survey_for_phy(phy)
+    }
+
+    /// Tune the interface to a frequency, see [`Socket::set_frequency`]
+    pub fn set_frequency(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        frequency_offset: Option<u32>,
+    ) -> Result<(), NlError> {
+        self.commands
+            .set_frequency(interface_index, frequency, frequency_offset)
+    }
+
+    /// Connect the interface to a network, see [`Socket::connect_network`]
+    pub fn connect_network(
+        &mut self,
+        interface_index: i32,
+        params: &ConnectParams,
+    ) -> Result<(), NlError> {
+        self.commands.connect_network(interface_index, params)
+    }
+
+    /// Disconnect the interface from the current network, see [`Socket::disconnect`]
+    pub fn disconnect(&mut self, interface_index: i32) -> Result<(), NlError> {
+        self.commands.disconnect(interface_index)
+    }
+
+    /// Create a new virtual interface on a wiphy, see [`Socket::new_interface`]
+    ///
+    /// With `socket_owner`, the interface lives as long as the command socket.
+    pub fn new_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        self.commands.new_interface(phy, name, iftype, socket_owner)
+    }
+
+    /// Create a monitor interface, see [`Socket::new_monitor_interface`]
+    ///
+    /// With `socket_owner`, the interface lives as long as the command socket.
+    pub fn new_monitor_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        flags: &[Nl80211MntrFlags],
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        self.commands
+            .new_monitor_interface(phy, name, flags, socket_owner)
+    }
+
+    /// Transmit a management frame, see [`Socket::send_frame`]
+    pub fn send_frame(
+        &mut self,
+        interface_index: i32,
+        frequency: Option<u32>,
+        frame: &[u8],
+        wait_for_ack: bool,
+    ) -> Result<Option<u64>, NlError> {
+        self.commands
+            .send_frame(interface_index, frequency, frame, wait_for_ack)
+    }
+
+    /// Stay on a channel for a while on an interface, see [`Socket::remain_on_channel`]
+    pub fn remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        duration: u32,
+    ) -> Result<u64, NlError> {
+        self.commands
+            .remain_on_channel(interface_index, frequency, duration)
+    }
+
+    /// End a remain-on-channel early, see [`Socket::cancel_remain_on_channel`]
+    pub fn cancel_remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        cookie: u64,
+    ) -> Result<(), NlError> {
+        self.commands
+            .cancel_remain_on_channel(interface_index, cookie)
+    }
+
+    /// Remove a virtual interface, see [`Socket::del_interface`]
+    pub fn del_interface(&mut self, interface_index: i32) -> Result<(), NlError> {
+        self.commands.del_interface(interface_index)
+    }
+
+    /// Start operating as an access point, see [`Socket::start_ap`]
+    pub fn start_ap(&mut self, interface_index: i32, config: &ApConfig) -> Result<(), NlError> {
+        self.commands.start_ap(interface_index, config)
+    }
+
+    /// Stop operating as an access point, see [`Socket::stop_ap`]
+    pub fn stop_ap(&mut self, interface_index: i32) -> Result<(), NlError> {
+        self.commands.stop_ap(interface_index)
+    }
+
+    /// Change the BSS parameters of an AP, see [`Socket::set_bss`]
+    pub fn set_bss(&mut self, interface_index: i32, config: &BssConfig) -> Result<(), NlError> {
+        self.commands.set_bss(interface_index, config)
+    }
+
+    /// Get the transmit power level of an interface, see [`Socket::get_tx_power`]
+    pub fn get_tx_power(&mut self, interface_index: i32) -> Result<Option<Mbm>, NlError> {
+        self.commands.get_tx_power(interface_index)
+    }
+
+    /// Set the transmit power of an interface, see [`Socket::set_tx_power`]
+    pub fn set_tx_power(&mut self, interface_index: i32, power: TxPower) -> Result<(), NlError> {
+        self.commands.set_tx_power(interface_index, power)
+    }
+
+    /// Set the coverage class of a wiphy, see [`Socket::set_coverage_class`]
+    pub fn set_coverage_class(&mut self, phy: u32, coverage_class: u8) -> Result<(), NlError> {
+        self.commands.set_coverage_class(phy, coverage_class)
+    }
+
+    /// Set the coverage class of a wiphy for a link distance, see [`Socket::set_distance`]
+    pub fn set_distance(&mut self, phy: u32, distance: u32) -> Result<(), NlError> {
+        self.commands.set_distance(phy, distance)
+    }
+
+    /// Set the EDCA parameters of transmit queues, see [`Socket::set_txq_params`]
+    pub fn set_txq_params(
+        &mut self,
+        interface_index: i32,
+        params: &[TxqParams],
+    ) -> Result<(), NlError> {
+        self.commands.set_txq_params(interface_index, params)
+    }
+
+    /// Change the limits of the transmit queues of a wiphy, see [`Socket::set_txq_limits`]
+    pub fn set_txq_limits(&mut self, phy: u32, limits: &TxqLimits) -> Result<(), NlError> {
+        self.commands.set_txq_limits(phy, limits)
+    }
+
+    /// Get the link state of a network interface, see [`Socket::get_link_state`]
+    #[cfg(feature = "rtnetlink")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rtnetlink")))]
+    pub fn get_link_state(&mut self, interface_index: i32) -> Result<crate::LinkState, NlError> {
+        self.commands.get_link_state(interface_index)
+    }
+
+    /// Bring a network interface up or down, see [`Socket::set_interface_up`]
+    #[cfg(feature = "rtnetlink")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rtnetlink")))]
+    pub fn set_interface_up(&mut self, interface_index: i32, up: bool) -> Result<(), NlError> {
+        self.commands.set_interface_up(interface_index, up)
+    }
+
+    /// Change the MAC address of a network interface, see [`Socket::set_interface_mac`]
+    #[cfg(feature = "rtnetlink")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rtnetlink")))]
+    pub fn set_interface_mac(&mut self, interface_index: i32, mac: &[u8]) -> Result<(), NlError> {
+        self.commands.set_interface_mac(interface_index, mac)
+    }
+}
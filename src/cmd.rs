@@ -7,6 +7,7 @@ use neli_proc_macros::neli_enum;
 ///
 /// Enumeration from nl80211/nl80211.h:880
 #[neli_enum(serialized_type = "u8")]
+#[non_exhaustive]
 pub enum Nl80211Cmd {
     CmdUnspec = 0,
     CmdGetWiphy = 1,
@@ -134,8 +135,45 @@ pub enum Nl80211Cmd {
     CmdDelNanFunction = 118,
     CmdChangeNanConfig = 119,
     CmdNanMatch = 120,
-    CmdAfterLast = 121,
-    CmdMax = 120,
+    CmdSetMulticastToUnicast = 121,
+    CmdUpdateConnectParams = 122,
+    CmdSetPmk = 123,
+    CmdDelPmk = 124,
+    CmdPortAuthorized = 125,
+    CmdReloadRegdb = 126,
+    CmdExternalAuth = 127,
+    CmdStaOpmodeChanged = 128,
+    CmdControlPortFrame = 129,
+    CmdGetFtmResponderStats = 130,
+    CmdPeerMeasurementStart = 131,
+    CmdPeerMeasurementResult = 132,
+    CmdPeerMeasurementComplete = 133,
+    CmdNotifyRadar = 134,
+    CmdUpdateOweInfo = 135,
+    CmdProbeMeshLink = 136,
+    CmdSetTidConfig = 137,
+    CmdUnprotBeacon = 138,
+    CmdControlPortFrameTxStatus = 139,
+    CmdSetSarSpecs = 140,
+    CmdObssColorCollision = 141,
+    CmdColorChangeRequest = 142,
+    CmdColorChangeStarted = 143,
+    CmdColorChangeAborted = 144,
+    CmdColorChangeCompleted = 145,
+    CmdSetFilsAad = 146,
+    CmdAssocComeback = 147,
+    CmdAddLink = 148,
+    CmdRemoveLink = 149,
+    CmdAddLinkSta = 150,
+    CmdModifyLinkSta = 151,
+    CmdRemoveLinkSta = 152,
+    CmdSetHwTimestamp = 153,
+    CmdLinksRemoved = 154,
+    CmdSetTidToLinkMapping = 155,
+    CmdAssocMloReconf = 156,
+    CmdEpcsCfg = 157,
+    CmdAfterLast = 158,
+    CmdMax = 157,
 }
 
 impl Cmd for Nl80211Cmd {}
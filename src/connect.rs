@@ -0,0 +1,440 @@
+use crate::attr::{Nl80211Attr, Nl80211AuthType, Nl80211Bandc, Nl80211BssSelectAttr};
+use crate::bss::Elements;
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// AKM suite selector for IEEE 802.1X authentication
+pub const AKM_SUITE_8021X: u32 = 0x000f_ac01;
+/// AKM suite selector for PSK authentication
+pub const AKM_SUITE_PSK: u32 = 0x000f_ac02;
+/// AKM suite selector for SAE authentication
+pub const AKM_SUITE_SAE: u32 = 0x000f_ac08;
+/// AKM suite selector for FILS with SHA-256
+pub const AKM_SUITE_FILS_SHA256: u32 = 0x000f_ac0e;
+/// AKM suite selector for FILS with SHA-384
+pub const AKM_SUITE_FILS_SHA384: u32 = 0x000f_ac0f;
+/// AKM suite selector for OWE (Opportunistic Wireless Encryption)
+pub const AKM_SUITE_OWE: u32 = 0x000f_ac12;
+
+/// Cipher suite selector for TKIP
+pub const CIPHER_SUITE_TKIP: u32 = 0x000f_ac02;
+/// Cipher suite selector for CCMP-128
+pub const CIPHER_SUITE_CCMP: u32 = 0x000f_ac04;
+/// Cipher suite selector for GCMP-128
+pub const CIPHER_SUITE_GCMP: u32 = 0x000f_ac08;
+
+/// Parameters of a connection request, see [`Socket::connect_network`](crate::Socket::connect_network)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConnectParams {
+    /// SSID of the network
+    pub ssid: Vec<u8>,
+    /// Restrict the connection to this BSSID
+    pub bssid: Option<Vec<u8>>,
+    /// Restrict the connection to this frequency (MHz)
+    pub frequency: Option<u32>,
+    /// Authentication type, the driver picks one if unset
+    pub auth_type: Option<Nl80211AuthType>,
+    /// Bitmask of `Nl80211WpaVersions`
+    pub wpa_versions: Option<u32>,
+    /// AKM suite selectors, e.g. [`AKM_SUITE_PSK`]
+    pub akm_suites: Vec<u32>,
+    /// Pairwise cipher suite selectors, e.g. [`CIPHER_SUITE_CCMP`]
+    pub pairwise_ciphers: Vec<u32>,
+    /// Group cipher suite selector
+    pub group_cipher: Option<u32>,
+    /// Extra information elements to add to the (Re)Association Request, e.g. the RSN element
+    /// or the OWE Diffie-Hellman parameter element
+    pub ie: Option<Vec<u8>>,
+    /// Pairwise master key, i.e. the PSK derived from the passphrase, to let the driver do the
+    /// 4-way handshake of a PSK network, requires
+    /// [`Wiphy::supports_psk_handshake_offload`](crate::Wiphy::supports_psk_handshake_offload)
+    pub pmk: Option<Vec<u8>>,
+    /// Let the driver do the 4-way handshake of an 802.1X network, the PMK being set after the
+    /// EAP authentication, requires
+    /// [`Wiphy::supports_1x_handshake_offload`](crate::Wiphy::supports_1x_handshake_offload)
+    pub want_1x_handshake_offload: bool,
+    /// FILS ERP credentials for fast initial link setup
+    pub fils_erp: Option<FilsErp>,
+    /// Connect to the OWE BSS advertised by an open BSS in OWE transition mode
+    pub owe_transition: Option<OweTransition>,
+    /// How the driver should pick a BSS when no BSSID is given, if it selects the BSS itself
+    pub bss_select: Option<BssSelect>,
+    /// Advertise radio resource management (802.11k) support to the AP, requires
+    /// [`Wiphy::supports_rrm`](crate::Wiphy::supports_rrm)
+    pub use_rrm: bool,
+    /// Stay connected only as long as the socket that requested the connection is open
+    pub socket_owner: bool,
+}
+
+/// BSS selection behaviour of drivers doing their own BSS selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BssSelect {
+    /// Pick the BSS with the highest signal
+    Rssi,
+    /// Prefer BSSs on this band over the others
+    BandPref(Nl80211Bandc),
+    /// Add `delta` (dB) to the signal of BSSs on `band` before comparing them
+    RssiAdjust { band: Nl80211Bandc, delta: i8 },
+}
+
+impl BssSelect {
+    fn attr(&self) -> Result<Nlattr<Nl80211Attr, Buffer>, SerError> {
+        let mut attr = Nlattr::new(false, false, Nl80211Attr::AttrBssSelect, Buffer::new())?;
+        let nested = match *self {
+            BssSelect::Rssi => {
+                Nlattr::new(false, false, Nl80211BssSelectAttr::BssSelectAttrRssi, ())?
+            }
+            BssSelect::BandPref(band) => Nlattr::new(
+                false,
+                false,
+                Nl80211BssSelectAttr::BssSelectAttrBandPref,
+                u32::from(u16::from(band)),
+            )?,
+            BssSelect::RssiAdjust { band, delta } => Nlattr::new(
+                false,
+                false,
+                Nl80211BssSelectAttr::BssSelectAttrRssiAdjust,
+                [u16::from(band) as u8, delta as u8].as_slice(),
+            )?,
+        };
+        attr.add_nested_attribute(&nested)?;
+        Ok(attr)
+    }
+}
+
+/// FILS EAP re-authentication protocol (ERP) credentials
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FilsErp {
+    /// EAP re-authentication username
+    pub username: Vec<u8>,
+    /// EAP re-authentication realm
+    pub realm: Vec<u8>,
+    /// Next sequence number to use in the EAP re-authentication
+    pub next_seq_num: u16,
+    /// Re-authentication root key
+    pub rrk: Vec<u8>,
+}
+
+/// Target of an OWE transition mode element
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OweTransition {
+    /// BSSID of the OWE BSS
+    pub bssid: Vec<u8>,
+    /// SSID of the OWE BSS
+    pub ssid: Vec<u8>,
+}
+
+impl OweTransition {
+    /// Find the OWE transition mode element in a list of information elements, such as
+    /// [`Bss::information_elements`](crate::Bss::information_elements).
+    pub fn from_ies(ies: &[u8]) -> Option<Self> {
+        const VENDOR_SPECIFIC_ELEMENT_ID: u8 = 221;
+        const WFA_OUI_TYPE: [u8; 4] = [0x50, 0x6f, 0x9a, 0x1c];
+
+        let data = Elements::new(ies)
+            .filter(|(id, _)| *id == VENDOR_SPECIFIC_ELEMENT_ID)
+            .find_map(|(_, data)| data.strip_prefix(&WFA_OUI_TYPE[..]))?;
+        let (bssid, data) = (data.get(..6)?, data.get(6..)?);
+        let (ssid_len, data) = data.split_first()?;
+        Some(Self {
+            bssid: bssid.to_vec(),
+            ssid: data.get(..*ssid_len as usize)?.to_vec(),
+        })
+    }
+}
+
+impl ConnectParams {
+    pub(crate) fn attrs(
+        &self,
+        interface_index: i32,
+    ) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+
+        match &self.owe_transition {
+            Some(owe) => {
+                attrs.push(Nlattr::new(
+                    false,
+                    false,
+                    Nl80211Attr::AttrSsid,
+                    owe.ssid.as_slice(),
+                )?);
+                attrs.push(Nlattr::new(
+                    false,
+                    false,
+                    Nl80211Attr::AttrMac,
+                    owe.bssid.as_slice(),
+                )?);
+            }
+            None => {
+                attrs.push(Nlattr::new(
+                    false,
+                    false,
+                    Nl80211Attr::AttrSsid,
+                    self.ssid.as_slice(),
+                )?);
+                if let Some(bssid) = &self.bssid {
+                    attrs.push(Nlattr::new(
+                        false,
+                        false,
+                        Nl80211Attr::AttrMac,
+                        bssid.as_slice(),
+                    )?);
+                }
+            }
+        }
+        if let Some(frequency) = self.frequency {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWiphyFreq,
+                frequency,
+            )?);
+        }
+        if let Some(auth_type) = self.auth_type {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)),
+            )?);
+        }
+
+        let mut akm_suites = self.akm_suites.clone();
+        if self.owe_transition.is_some() && !akm_suites.contains(&AKM_SUITE_OWE) {
+            akm_suites.push(AKM_SUITE_OWE);
+        }
+        if !akm_suites.is_empty() || !self.pairwise_ciphers.is_empty() {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrPrivacy, ())?);
+        }
+        if let Some(wpa_versions) = self.wpa_versions {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWpaVersions,
+                wpa_versions,
+            )?);
+        }
+        if !akm_suites.is_empty() {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrAkmSuites,
+                akm_suites,
+            )?);
+        }
+        if !self.pairwise_ciphers.is_empty() {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrCipherSuitesPairwise,
+                self.pairwise_ciphers.clone(),
+            )?);
+        }
+        if let Some(group_cipher) = self.group_cipher {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrCipherSuiteGroup,
+                group_cipher,
+            )?);
+        }
+        if let Some(ie) = &self.ie {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrIe,
+                ie.as_slice(),
+            )?);
+        }
+
+        if let Some(pmk) = &self.pmk {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrPmk,
+                pmk.as_slice(),
+            )?);
+        }
+        if self.want_1x_handshake_offload {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWant1x4wayHs,
+                (),
+            )?);
+        }
+
+        if let Some(erp) = &self.fils_erp {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrFilsErpUsername,
+                erp.username.as_slice(),
+            )?);
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrFilsErpRealm,
+                erp.realm.as_slice(),
+            )?);
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrFilsErpNextSeqNum,
+                erp.next_seq_num,
+            )?);
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrFilsErpRrk,
+                erp.rrk.as_slice(),
+            )?);
+        }
+
+        if let Some(bss_select) = &self.bss_select {
+            attrs.push(bss_select.attr()?);
+        }
+        if self.use_rrm {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrUseRrm, ())?);
+        }
+        if self.socket_owner {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrSocketOwner, ())?);
+        }
+
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod test_connect {
+    use super::*;
+    use neli::attr::Attribute;
+
+    #[test]
+    fn test_owe_transition_from_ies() {
+        let ies = vec![
+            0, 4, 111, 112, 101, 110, 221, 15, 80, 111, 154, 28, 2, 0, 0, 0, 0, 1, 4, 111, 119,
+            101, 50,
+        ];
+
+        let owe = OweTransition::from_ies(&ies).unwrap();
+        assert_eq!(owe.bssid, vec![2, 0, 0, 0, 0, 1]);
+        assert_eq!(owe.ssid, b"owe2".to_vec());
+
+        assert_eq!(OweTransition::from_ies(&ies[..6]), None);
+        assert_eq!(OweTransition::from_ies(&ies[..20]), None);
+    }
+
+    #[test]
+    fn test_attrs() {
+        let params = ConnectParams {
+            ssid: b"open".to_vec(),
+            auth_type: Some(Nl80211AuthType::AuthtypeFilsSk),
+            akm_suites: vec![AKM_SUITE_FILS_SHA256],
+            fils_erp: Some(FilsErp {
+                username: b"user".to_vec(),
+                realm: b"example.com".to_vec(),
+                next_seq_num: 7,
+                rrk: vec![1; 32],
+            }),
+            owe_transition: Some(OweTransition {
+                bssid: vec![2, 0, 0, 0, 0, 1],
+                ssid: b"owe".to_vec(),
+            }),
+            ..Default::default()
+        };
+
+        let attrs = params.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as_with_len::<Vec<u8>>(Nl80211Attr::AttrSsid)
+                .unwrap(),
+            b"owe".to_vec()
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as_with_len::<Vec<u32>>(Nl80211Attr::AttrAkmSuites)
+                .unwrap(),
+            vec![AKM_SUITE_FILS_SHA256, AKM_SUITE_OWE]
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrAuthType)
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u16>(Nl80211Attr::AttrFilsErpNextSeqNum)
+                .unwrap(),
+            7
+        );
+        assert_eq!(
+            attrs
+                .get_attribute(Nl80211Attr::AttrFilsErpRrk)
+                .unwrap()
+                .payload()
+                .as_ref(),
+            &[1; 32]
+        );
+    }
+
+    #[test]
+    fn test_bss_select_attrs() {
+        let params = ConnectParams {
+            ssid: b"home".to_vec(),
+            bss_select: Some(BssSelect::RssiAdjust {
+                band: Nl80211Bandc::Band5ghz,
+                delta: -6,
+            }),
+            ..Default::default()
+        };
+
+        let attrs = params.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        let select = attrs
+            .get_attribute(Nl80211Attr::AttrBssSelect)
+            .unwrap()
+            .get_attr_handle::<Nl80211BssSelectAttr>()
+            .unwrap();
+        assert_eq!(
+            select
+                .get_attribute(Nl80211BssSelectAttr::BssSelectAttrRssiAdjust)
+                .unwrap()
+                .payload()
+                .as_ref(),
+            &[1, (-6i8) as u8]
+        );
+    }
+
+    #[test]
+    fn test_pmk_attrs() {
+        let params = ConnectParams {
+            ssid: b"iot".to_vec(),
+            akm_suites: vec![AKM_SUITE_PSK],
+            pmk: Some(vec![7; 32]),
+            ..Default::default()
+        };
+
+        let attrs = params.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attribute(Nl80211Attr::AttrPmk)
+                .unwrap()
+                .payload()
+                .as_ref(),
+            &[7; 32]
+        );
+        assert!(attrs.get_attribute(Nl80211Attr::AttrWant1x4wayHs).is_none());
+    }
+}
@@ -0,0 +1,488 @@
+use crate::attr::{Attrs, Nl80211Attr, Nl80211AttrCqm, Nl80211CqmRssiThresholdEvent, PayloadExt};
+use crate::cmd::Nl80211Cmd;
+use crate::interface::Interface;
+use crate::reason::{ReasonCode, StatusCode};
+use crate::scan::ScanInfo;
+
+use neli::attr::Attribute;
+use neli::err::DeError;
+use neli::genl::Genlmsghdr;
+
+/// Frame type of probe requests, to be used with [`Socket::register_frame`](crate::Socket::register_frame)
+pub const FRAME_TYPE_PROBE_REQ: u16 = 0x0040;
+/// Frame type of beacons
+pub const FRAME_TYPE_BEACON: u16 = 0x0080;
+/// Frame type of disassociations
+pub const FRAME_TYPE_DISASSOC: u16 = 0x00a0;
+/// Frame type of deauthentications
+pub const FRAME_TYPE_DEAUTH: u16 = 0x00c0;
+/// Frame type of action frames, to be used with [`Socket::register_frame`](crate::Socket::register_frame)
+pub const FRAME_TYPE_ACTION: u16 = 0x00d0;
+
+/// Action frame category of radio measurement (802.11k) frames, to be used with
+/// [`Socket::register_action_frames`](crate::Socket::register_action_frames)
+pub const ACTION_CATEGORY_RADIO_MEASUREMENT: u8 = 5;
+/// Action frame category of wireless network management (802.11v) frames
+pub const ACTION_CATEGORY_WNM: u8 = 10;
+/// Radio measurement action of a Radio Measurement Request
+pub const RADIO_MEASUREMENT_REQUEST: u8 = 0;
+/// Radio measurement action of a Neighbor Report Response
+pub const RADIO_MEASUREMENT_NEIGHBOR_REPORT_RESPONSE: u8 = 5;
+/// WNM action of a BSS Transition Management Request
+pub const WNM_BSS_TRANSITION_REQUEST: u8 = 7;
+
+/// A notification sent by nl80211, either to a multicast group the socket subscribed to
+/// or directly to the socket (e.g. registered frames)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A management frame was received
+    Frame(Frame),
+    /// A connection attempt completed, successfully or not
+    Connect(ConnectResult),
+    /// The interface was disconnected from its network
+    Disconnect(Disconnect),
+    /// A deauthentication frame was sent or received, see [`Frame::reason_code`]
+    Deauthenticate(Frame),
+    /// A disassociation frame was sent or received, see [`Frame::reason_code`]
+    Disassociate(Frame),
+    /// A virtual interface was created, e.g. when a driver is loaded, sent on the "config"
+    /// multicast group
+    InterfaceAdded(Interface),
+    /// A virtual interface was removed
+    InterfaceRemoved(Interface),
+    /// A scan completed or was aborted, its results can be fetched with
+    /// [`Socket::get_bss_info`](crate::Socket::get_bss_info)
+    ScanDone(ScanInfo),
+    /// A station associated with an access point interface
+    StationJoined(StationEvent),
+    /// A station left an access point interface
+    StationLeft(StationEvent),
+    /// The interface stopped receiving beacons from its access point, sent on the "mlme"
+    /// multicast group, usually followed by a disconnection if the beacons don't come back
+    BeaconLoss(CqmNotification),
+    /// Frames sent to a peer were not acknowledged, see [`CqmNotification::lost_packets`]
+    LowAck(CqmNotification),
+    /// A notification which is not decoded by this crate
+    Other(Nl80211Cmd),
+    /// The socket's receive buffer overflowed and some notifications were dropped by the
+    /// kernel, any state tracked from events should be fetched again
+    EventsLost,
+}
+
+impl TryFrom<&Genlmsghdr<Nl80211Cmd, Nl80211Attr>> for Event {
+    type Error = DeError;
+
+    fn try_from(msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let attrs = msg.get_attr_handle();
+        Ok(match msg.cmd {
+            Nl80211Cmd::CmdFrame => Event::Frame(attrs.try_into()?),
+            Nl80211Cmd::CmdConnect => Event::Connect(attrs.try_into()?),
+            Nl80211Cmd::CmdDisconnect => Event::Disconnect(attrs.try_into()?),
+            Nl80211Cmd::CmdDeauthenticate => Event::Deauthenticate(attrs.try_into()?),
+            Nl80211Cmd::CmdDisassociate => Event::Disassociate(attrs.try_into()?),
+            Nl80211Cmd::CmdNewInterface => Event::InterfaceAdded(attrs.try_into()?),
+            Nl80211Cmd::CmdDelInterface => Event::InterfaceRemoved(attrs.try_into()?),
+            Nl80211Cmd::CmdNewScanResults => Event::ScanDone(attrs.try_into()?),
+            Nl80211Cmd::CmdScanAborted => Event::ScanDone(ScanInfo {
+                aborted: true,
+                ..attrs.try_into()?
+            }),
+            Nl80211Cmd::CmdNewStation => Event::StationJoined(attrs.try_into()?),
+            Nl80211Cmd::CmdDelStation => Event::StationLeft(attrs.try_into()?),
+            Nl80211Cmd::CmdNotifyCqm => {
+                let notification = CqmNotification::try_from(attrs)?;
+                if notification.beacon_loss {
+                    Event::BeaconLoss(notification)
+                } else if notification.lost_packets.is_some() {
+                    Event::LowAck(notification)
+                } else {
+                    Event::Other(msg.cmd)
+                }
+            }
+            cmd => Event::Other(cmd),
+        })
+    }
+}
+
+/// A management frame received by an interface
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Index of the interface which received the frame
+    pub index: Option<i32>,
+    /// Frequency the frame was received on (MHz)
+    pub frequency: Option<u32>,
+    /// Signal strength of the frame (dBm)
+    pub signal: Option<i32>,
+    /// The frame, starting with the 802.11 header
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    /// Frame type and subtype, in the same format as [`FRAME_TYPE_PROBE_REQ`] and friends
+    pub fn frame_type(&self) -> Option<u16> {
+        self.data.first().map(|fc| u16::from(*fc) & 0xfc)
+    }
+
+    /// Transmitter address of the frame
+    pub fn source(&self) -> Option<&[u8]> {
+        self.data.get(10..16)
+    }
+
+    /// Category and action code of an action frame, e.g. ([`ACTION_CATEGORY_WNM`],
+    /// [`WNM_BSS_TRANSITION_REQUEST`])
+    pub fn action(&self) -> Option<(u8, u8)> {
+        if self.frame_type()? != FRAME_TYPE_ACTION {
+            return None;
+        }
+        match self.data.get(24..26)? {
+            [category, action] => Some((*category, *action)),
+            _ => None,
+        }
+    }
+
+    /// Reason code of a deauthentication or disassociation frame
+    pub fn reason_code(&self) -> Option<ReasonCode> {
+        match self.frame_type()? {
+            FRAME_TYPE_DEAUTH | FRAME_TYPE_DISASSOC => match self.data.get(24..26)? {
+                [low, high] => Some(u16::from_le_bytes([*low, *high]).into()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Frame {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrWiphyFreq => res.frequency = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrRxSignalDbm => res.signal = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrFrame => res.data = attr.get_payload_as_with_len()?,
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Outcome of a connection attempt, see
+/// [`Socket::connect_network`](crate::Socket::connect_network)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConnectResult {
+    /// Index of the interface
+    pub index: Option<i32>,
+    /// BSSID of the access point
+    pub bssid: Option<Vec<u8>>,
+    /// Status sent by the access point, or generated locally on failure
+    pub status: Option<StatusCode>,
+    /// No response was received from the access point
+    pub timed_out: bool,
+    /// Information elements of the (re)association response
+    pub response_ies: Option<Vec<u8>>,
+}
+
+impl ConnectResult {
+    /// Whether the interface is now connected
+    pub fn is_success(&self) -> bool {
+        !self.timed_out && self.status.is_some_and(|status| status.is_success())
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for ConnectResult {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrMac => res.bssid = Some(attr.get_mac()?),
+                Nl80211Attr::AttrStatusCode => {
+                    res.status = Some(attr.get_payload_exact::<u16>()?.into())
+                }
+                Nl80211Attr::AttrTimedOut => res.timed_out = true,
+                Nl80211Attr::AttrRespIe => res.response_ies = Some(attr.get_payload_as_with_len()?),
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// The interface was disconnected from its network
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Disconnect {
+    /// Index of the interface
+    pub index: Option<i32>,
+    /// Reason of the disconnection
+    pub reason: Option<ReasonCode>,
+    /// The disconnection was initiated by the access point, rather than locally
+    pub by_ap: bool,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Disconnect {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrReasonCode => {
+                    res.reason = Some(attr.get_payload_exact::<u16>()?.into())
+                }
+                Nl80211Attr::AttrDisconnectedByAp => res.by_ap = true,
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// A station joining or leaving an access point interface, sent on the "mlme" multicast group
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StationEvent {
+    /// Index of the access point interface
+    pub index: Option<i32>,
+    /// MAC address of the station
+    pub mac: Option<Vec<u8>>,
+    /// Information elements of the (re)association request, when the station joined
+    pub association_ies: Option<Vec<u8>>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for StationEvent {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrMac => res.mac = Some(attr.get_mac()?),
+                Nl80211Attr::AttrIe => res.association_ies = Some(attr.get_payload_as_with_len()?),
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Connection quality monitor notification of an interface
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CqmNotification {
+    /// Index of the interface
+    pub index: Option<i32>,
+    /// MAC address of the peer, e.g. the access point
+    pub mac: Option<Vec<u8>>,
+    /// Beacons from the access point are no longer received
+    pub beacon_loss: bool,
+    /// Number of consecutive frames the peer didn't acknowledge
+    pub lost_packets: Option<u32>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for CqmNotification {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrMac => res.mac = Some(attr.get_mac()?),
+                Nl80211Attr::AttrCqm => {
+                    let cqm = attr.get_attr_handle::<Nl80211AttrCqm>()?;
+                    for attr in cqm.iter() {
+                        match attr.nla_type.nla_type {
+                            Nl80211AttrCqm::AttrCqmBeaconLossEvent => res.beacon_loss = true,
+                            Nl80211AttrCqm::AttrCqmPktLossEvent => {
+                                res.lost_packets = Some(attr.get_payload_exact()?)
+                            }
+                            // Older kernels report beacon loss as an RSSI event
+                            Nl80211AttrCqm::AttrCqmRssiThresholdEvent => {
+                                let event: u32 = attr.get_payload_exact()?;
+                                res.beacon_loss |= event
+                                    == u32::from(u16::from(
+                                        Nl80211CqmRssiThresholdEvent::CqmRssiBeaconLossEvent,
+                                    ));
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_event {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::genl::Nlattr;
+    use neli::types::GenlBuffer;
+
+    #[test]
+    fn test_parse_frame() {
+        let probe_req = vec![
+            64, 0, 0, 0, 255, 255, 255, 255, 255, 255, 2, 0, 0, 0, 0, 1, 255, 255, 255, 255, 255,
+            255, 16, 0, 0, 0,
+        ];
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrWiphy, 0u32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 5i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrWiphyFreq, 2412u32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrRxSignalDbm, -48i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrFrame, probe_req.clone()).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdFrame, 1, attrs);
+
+        let event = Event::try_from(&msg).unwrap();
+        let expected_frame = Frame {
+            index: Some(5),
+            frequency: Some(2412),
+            signal: Some(-48),
+            data: probe_req,
+        };
+        assert_eq!(event, Event::Frame(expected_frame.clone()));
+        assert_eq!(expected_frame.frame_type(), Some(FRAME_TYPE_PROBE_REQ));
+        assert_eq!(expected_frame.source(), Some(&[2, 0, 0, 0, 0, 1][..]));
+        assert_eq!(expected_frame.action(), None);
+
+        let mut btm_request = vec![0; 24];
+        btm_request[0] = FRAME_TYPE_ACTION as u8;
+        btm_request.extend([ACTION_CATEGORY_WNM, WNM_BSS_TRANSITION_REQUEST, 1]);
+        let action = Frame {
+            data: btm_request,
+            ..Default::default()
+        };
+        assert_eq!(
+            action.action(),
+            Some((ACTION_CATEGORY_WNM, WNM_BSS_TRANSITION_REQUEST))
+        );
+
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdRegChange, 1, GenlBuffer::new());
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::Other(Nl80211Cmd::CmdRegChange)
+        );
+    }
+
+    #[test]
+    fn test_parse_connection_events() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 3i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrMac, &[2u8, 0, 0, 0, 0, 1][..]).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrStatusCode, 1u16).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrTimedOut, ()).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdConnect, 1, attrs);
+        let expected = ConnectResult {
+            index: Some(3),
+            bssid: Some(vec![2, 0, 0, 0, 0, 1]),
+            status: Some(StatusCode::UnspecifiedFailure),
+            timed_out: true,
+            response_ies: None,
+        };
+        assert!(!expected.is_success());
+        assert_eq!(Event::try_from(&msg).unwrap(), Event::Connect(expected));
+
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 3i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrReasonCode, 15u16).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrDisconnectedByAp, ()).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdDisconnect, 1, attrs);
+        let event = Event::try_from(&msg).unwrap();
+        assert_eq!(
+            event,
+            Event::Disconnect(Disconnect {
+                index: Some(3),
+                reason: Some(ReasonCode::FourwayHandshakeTimeout),
+                by_ap: true,
+            })
+        );
+
+        let mut deauth = vec![0; 24];
+        deauth[0] = FRAME_TYPE_DEAUTH as u8;
+        deauth.extend([3, 0]);
+        let frame = Frame {
+            data: deauth,
+            ..Default::default()
+        };
+        assert_eq!(frame.reason_code(), Some(ReasonCode::DeauthLeaving));
+    }
+
+    #[test]
+    fn test_parse_cqm_events() {
+        let mut cqm = Nlattr::new(false, false, AttrCqm, neli::types::Buffer::new()).unwrap();
+        cqm.add_nested_attribute(
+            &Nlattr::new(false, false, Nl80211AttrCqm::AttrCqmPktLossEvent, 32u32).unwrap(),
+        )
+        .unwrap();
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 3i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrMac, &[2u8, 0, 0, 0, 0, 1][..]).unwrap());
+        attrs.push(cqm);
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNotifyCqm, 1, attrs);
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::LowAck(CqmNotification {
+                index: Some(3),
+                mac: Some(vec![2, 0, 0, 0, 0, 1]),
+                beacon_loss: false,
+                lost_packets: Some(32),
+            })
+        );
+
+        let mut cqm = Nlattr::new(false, false, AttrCqm, neli::types::Buffer::new()).unwrap();
+        cqm.add_nested_attribute(
+            &Nlattr::new(false, false, Nl80211AttrCqm::AttrCqmBeaconLossEvent, ()).unwrap(),
+        )
+        .unwrap();
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 3i32).unwrap());
+        attrs.push(cqm);
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNotifyCqm, 1, attrs);
+        assert!(matches!(
+            Event::try_from(&msg).unwrap(),
+            Event::BeaconLoss(CqmNotification {
+                beacon_loss: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_station_events() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 4i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrMac, &[2u8, 0, 0, 0, 0, 2][..]).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrIe, &[0u8, 2, b'a', b'p'][..]).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNewStation, 1, attrs);
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::StationJoined(StationEvent {
+                index: Some(4),
+                mac: Some(vec![2, 0, 0, 0, 0, 2]),
+                association_ies: Some(vec![0, 2, b'a', b'p']),
+            })
+        );
+
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, AttrIfindex, 4i32).unwrap());
+        attrs.push(Nlattr::new(false, false, AttrMac, &[2u8, 0, 0, 0, 0, 2][..]).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdDelStation, 1, attrs);
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::StationLeft(StationEvent {
+                index: Some(4),
+                mac: Some(vec![2, 0, 0, 0, 0, 2]),
+                association_ies: None,
+            })
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::attr::{Attrs, Nl80211Attr};
+use crate::attr::{Attrs, Nl80211Attr, Nl80211Bandc, Nl80211ChanWidth, PayloadExt};
+use crate::power::Mbm;
+use crate::wiphy::Wiphy;
 
 use neli::attr::Attribute;
 use neli::err::DeError;
@@ -16,14 +18,55 @@ pub struct Interface {
     pub name: Option<Vec<u8>>,
     /// Interface frequency of the selected channel (MHz)
     pub frequency: Option<u32>,
+    /// Offset of the selected channel frequency in KHz, used by S1G (sub-1 GHz) channels
+    pub frequency_offset: Option<u32>,
     /// Interface chanel
     pub channel: Option<u32>,
+    /// Width of the selected channel
+    pub channel_width: Option<Nl80211ChanWidth>,
+    /// Center frequency of the whole channel for 40 MHz and wider channels (MHz)
+    pub center_frequency1: Option<u32>,
+    /// Center frequency of the second segment of 80+80 MHz channels (MHz)
+    pub center_frequency2: Option<u32>,
+    /// Bitmap of the punctured (disabled) 20 MHz subchannels of the channel, lowest frequency
+    /// first, used by EHT (Wi-Fi 7)
+    pub punct_bitmap: Option<u32>,
     /// Interface transmit power level in signed mBm units.
-    pub power: Option<u32>,
-    /// index of wiphy to operate on, cf. /sys/class/ieee80211/<phyname>/index
+    pub power: Option<i32>,
+    /// index of wiphy to operate on, cf. `/sys/class/ieee80211/<phyname>/index`
     pub phy: Option<u32>,
     /// Wireless device identifier, used for pseudo-devices that don't have a netdev
     pub device: Option<u64>,
+    /// Link state of the network interface, only filled with the `rtnetlink` feature
+    pub link_state: Option<LinkState>,
+}
+
+impl Interface {
+    /// Band of the selected channel
+    pub fn band(&self) -> Option<Nl80211Bandc> {
+        self.frequency.and_then(Nl80211Bandc::from_frequency)
+    }
+
+    /// Transmit power level of the interface
+    pub fn tx_power(&self) -> Option<Mbm> {
+        self.power.map(Mbm)
+    }
+
+    /// Wiphy of the interface among those returned by
+    /// [`Socket::get_wiphys`](crate::Socket::get_wiphys)
+    pub fn find_wiphy<'a>(&self, wiphys: &'a [Wiphy]) -> Option<&'a Wiphy> {
+        let phy = self.phy?;
+        wiphys.iter().find(|wiphy| wiphy.index == Some(phy))
+    }
+}
+
+/// Link state of a network interface
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LinkState {
+    /// The interface is administratively up (`IFF_UP`)
+    pub up: bool,
+    /// The interface has a carrier (`IFF_LOWER_UP`), e.g. a station interface is connected
+    pub carrier: bool,
 }
 
 impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
@@ -34,28 +77,42 @@ impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
         for attr in attrs.iter() {
             match attr.nla_type.nla_type {
                 Nl80211Attr::AttrIfindex => {
-                    res.index = Some(attr.get_payload_as()?);
+                    res.index = Some(attr.get_payload_exact()?);
                 }
                 Nl80211Attr::AttrSsid => {
                     res.ssid = Some(attr.get_payload_as_with_len()?);
                 }
                 Nl80211Attr::AttrMac => {
-                    res.mac = Some(attr.get_payload_as_with_len()?);
+                    res.mac = Some(attr.get_mac()?);
                 }
                 Nl80211Attr::AttrIfname => {
                     res.name = Some(attr.get_payload_as_with_len()?);
                 }
                 Nl80211Attr::AttrWiphyFreq => {
-                    res.frequency = Some(attr.get_payload_as()?);
+                    res.frequency = Some(attr.get_payload_exact()?);
+                }
+                Nl80211Attr::AttrWiphyFreqOffset => {
+                    res.frequency_offset = Some(attr.get_payload_exact()?);
                 }
                 Nl80211Attr::AttrChannelWidth => {
-                    res.channel = Some(attr.get_payload_as()?);
+                    let width: u32 = attr.get_payload_exact()?;
+                    res.channel = Some(width);
+                    res.channel_width = u16::try_from(width).ok().map(Nl80211ChanWidth::from);
+                }
+                Nl80211Attr::AttrCenterFreq1 => {
+                    res.center_frequency1 = Some(attr.get_payload_exact()?);
+                }
+                Nl80211Attr::AttrCenterFreq2 => {
+                    res.center_frequency2 = Some(attr.get_payload_exact()?);
+                }
+                Nl80211Attr::AttrPunctBitmap => {
+                    res.punct_bitmap = Some(attr.get_payload_exact()?);
                 }
                 Nl80211Attr::AttrWiphyTxPowerLevel => {
-                    res.power = Some(attr.get_payload_as()?);
+                    res.power = Some(attr.get_payload_exact()?);
                 }
-                Nl80211Attr::AttrWiphy => res.phy = Some(attr.get_payload_as()?),
-                Nl80211Attr::AttrWdev => res.device = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrWiphy => res.phy = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrWdev => res.device = Some(attr.get_payload_exact()?),
                 _ => (),
             }
         }
@@ -107,12 +164,96 @@ mod test_interface {
             mac: Some(vec![255, 255, 255, 255, 255, 255]),
             name: Some(vec![119, 108, 112, 53, 115, 48]),
             frequency: Some(u32::from_le_bytes([108, 9, 0, 0])),
+            frequency_offset: None,
             channel: Some(u32::from_le_bytes([1, 0, 0, 0])),
-            power: Some(u32::from_le_bytes([164, 6, 0, 0])),
+            channel_width: Some(Nl80211ChanWidth::ChanWidth20),
+            center_frequency1: None,
+            center_frequency2: None,
+            punct_bitmap: None,
+            power: Some(i32::from_le_bytes([164, 6, 0, 0])),
             phy: Some(u32::from_le_bytes([0, 0, 0, 0])),
             device: Some(u64::from_le_bytes([1, 0, 0, 0, 0, 0, 0, 0])),
+            link_state: None,
         };
 
         assert_eq!(interface, expected_interface)
     }
+
+    #[test]
+    fn test_parser_frequency_offset() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![4, 0, 0, 0]),
+            new_attr(AttrWiphyFreq, vec![148, 3, 0, 0]),
+            new_attr(AttrWiphyFreqOffset, vec![244, 1, 0, 0]),
+        ];
+
+        let interface: Interface = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+
+        assert_eq!(interface.frequency, Some(916));
+        assert_eq!(interface.frequency_offset, Some(500));
+    }
+
+    #[test]
+    fn test_parser_320mhz() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![4, 0, 0, 0]),
+            new_attr(AttrWiphyFreq, vec![227, 23, 0, 0]),
+            new_attr(AttrChannelWidth, vec![13, 0, 0, 0]),
+            new_attr(AttrCenterFreq1, vec![121, 24, 0, 0]),
+            new_attr(AttrPunctBitmap, vec![3, 0, 0, 0]),
+        ];
+
+        let interface: Interface = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            interface.channel_width,
+            Some(Nl80211ChanWidth::ChanWidth320)
+        );
+        assert_eq!(interface.center_frequency1, Some(6265));
+        assert_eq!(interface.punct_bitmap, Some(3));
+        assert_eq!(interface.band(), Some(Nl80211Bandc::Band6ghz));
+    }
+
+    #[test]
+    fn test_parser_invalid_length() {
+        for attr in [
+            new_attr(AttrIfindex, vec![3, 0]),
+            new_attr(AttrWiphyFreq, vec![108, 9, 0, 0, 0, 0, 0, 0]),
+            new_attr(AttrMac, vec![255, 255, 255, 255, 255]),
+        ] {
+            let res: Result<Interface, _> =
+                AttrHandle::new(vec![attr].into_iter().collect()).try_into();
+            assert!(res.is_err());
+        }
+    }
+
+    #[test]
+    fn test_find_wiphy() {
+        let wiphys = vec![
+            Wiphy {
+                index: Some(0),
+                ..Default::default()
+            },
+            Wiphy {
+                index: Some(1),
+                name: Some("phy1".to_string()),
+                ..Default::default()
+            },
+        ];
+        let interface = Interface {
+            phy: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            interface
+                .find_wiphy(&wiphys)
+                .and_then(|w| w.name.as_deref()),
+            Some("phy1")
+        );
+        assert_eq!(Interface::default().find_wiphy(&wiphys), None);
+    }
 }
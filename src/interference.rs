@@ -0,0 +1,202 @@
+use crate::survey::{merge_surveys, Survey};
+use crate::Socket;
+
+use neli::err::NlError;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Limits above which an [`InterferenceWatcher`] reports a channel
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct InterferenceThresholds {
+    /// Ratio of the time the channel was sensed busy between two samples, from 0 to 1
+    pub busy_ratio: Option<f64>,
+    /// Noise floor of the channel (dBm)
+    pub noise: Option<i8>,
+}
+
+/// Kind of interference reported by an [`InterferenceWatcher`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterferenceKind {
+    /// The channel was busy for this ratio of the time between two samples
+    ChannelBusy(f64),
+    /// The noise floor of the channel (dBm)
+    HighNoise(i8),
+}
+
+/// A channel exceeding one of the [`InterferenceThresholds`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterferenceEvent {
+    /// Center frequency of the channel (MHz)
+    pub frequency: u32,
+    /// The wiphy is currently operating on this channel
+    pub in_use: bool,
+    /// Which limit was exceeded
+    pub kind: InterferenceKind,
+}
+
+/// Sample the channel survey of an interface at a fixed interval and report the channels which
+/// are too busy or too noisy, e.g. to trigger a channel re-selection on an access point
+///
+/// Channels are reported at each sample for as long as they exceed a limit. The busy ratio is
+/// computed from the counters of two consecutive samples, so it is only checked from the second
+/// sample of a channel.
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{InterferenceThresholds, InterferenceWatcher, Socket};
+/// use std::time::Duration;
+///
+/// let mut socket = Socket::connect()?;
+/// let thresholds = InterferenceThresholds {
+///     busy_ratio: Some(0.6),
+///     noise: Some(-80),
+/// };
+/// let mut watcher = InterferenceWatcher::new(3, Duration::from_secs(10), thresholds);
+/// loop {
+///     for event in watcher.poll(&mut socket)? {
+///         println!("{} MHz: {:?}", event.frequency, event.kind);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct InterferenceWatcher {
+    interface_index: i32,
+    interval: Duration,
+    thresholds: InterferenceThresholds,
+    last: Option<(Instant, Vec<Survey>)>,
+}
+
+impl InterferenceWatcher {
+    /// Create a watcher for the channels surveyed by the wiphy of an interface
+    pub fn new(
+        interface_index: i32,
+        interval: Duration,
+        thresholds: InterferenceThresholds,
+    ) -> Self {
+        Self {
+            interface_index,
+            interval,
+            thresholds,
+            last: None,
+        }
+    }
+
+    /// Wait until the interval has elapsed since the previous sample, then sample the survey
+    pub fn poll(&mut self, socket: &mut Socket) -> Result<Vec<InterferenceEvent>, NlError> {
+        if let Some((last, _)) = &self.last {
+            if let Some(remaining) = self.interval.checked_sub(last.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+        let surveys = socket.get_survey(self.interface_index)?;
+        Ok(self.update(surveys, Instant::now()))
+    }
+
+    /// Record a survey taken at `now`, e.g. from an async socket, and check it against the
+    /// thresholds
+    pub fn update(&mut self, surveys: Vec<Survey>, now: Instant) -> Vec<InterferenceEvent> {
+        let surveys = merge_surveys(surveys);
+        let mut events = Vec::new();
+        for survey in &surveys {
+            let frequency = match survey.frequency {
+                Some(frequency) => frequency,
+                None => continue,
+            };
+            let mut report = |kind| {
+                events.push(InterferenceEvent {
+                    frequency,
+                    in_use: survey.in_use,
+                    kind,
+                })
+            };
+
+            let previous = self.last.as_ref().and_then(|(_, previous)| {
+                previous.iter().find(|s| {
+                    (s.frequency, s.frequency_offset) == (survey.frequency, survey.frequency_offset)
+                })
+            });
+            if let (Some(limit), Some(ratio)) = (
+                self.thresholds.busy_ratio,
+                previous.and_then(|previous| busy_ratio(previous, survey)),
+            ) {
+                if ratio > limit {
+                    report(InterferenceKind::ChannelBusy(ratio));
+                }
+            }
+            if let (Some(limit), Some(noise)) = (self.thresholds.noise, survey.noise) {
+                if noise > limit {
+                    report(InterferenceKind::HighNoise(noise));
+                }
+            }
+        }
+        self.last = Some((now, surveys));
+        events
+    }
+}
+
+/// Ratio of busy time between two surveys of the same channel, `None` if the counters didn't
+/// move forward
+fn busy_ratio(previous: &Survey, current: &Survey) -> Option<f64> {
+    let time = current.time?.checked_sub(previous.time?)?;
+    let busy = current.time_busy?.checked_sub(previous.time_busy?)?;
+    (time > 0).then(|| (busy as f64 / time as f64).min(1.0))
+}
+
+#[cfg(test)]
+mod test_interference {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let start = Instant::now();
+        let thresholds = InterferenceThresholds {
+            busy_ratio: Some(0.5),
+            noise: Some(-85),
+        };
+        let mut watcher = InterferenceWatcher::new(3, Duration::from_secs(1), thresholds);
+        let survey = |frequency, noise, time, time_busy| Survey {
+            frequency: Some(frequency),
+            noise: Some(noise),
+            in_use: frequency == 2412,
+            time: Some(time),
+            time_busy: Some(time_busy),
+            ..Default::default()
+        };
+
+        let events = watcher.update(
+            vec![survey(2412, -95, 1_000, 100), survey(2437, -80, 1_000, 900)],
+            start,
+        );
+        assert_eq!(
+            events,
+            vec![InterferenceEvent {
+                frequency: 2437,
+                in_use: false,
+                kind: InterferenceKind::HighNoise(-80),
+            }]
+        );
+
+        let events = watcher.update(
+            vec![
+                survey(2412, -95, 2_000, 800),
+                survey(2437, -90, 2_000, 1_000),
+            ],
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(
+            events,
+            vec![InterferenceEvent {
+                frequency: 2412,
+                in_use: true,
+                kind: InterferenceKind::ChannelBusy(0.7),
+            }]
+        );
+
+        // Counters reset
+        let events = watcher.update(vec![survey(2412, -95, 10, 10)], start);
+        assert!(events.is_empty());
+    }
+}
@@ -7,18 +7,92 @@ pub use cmd::*;
 mod attr;
 pub use attr::*;
 
+mod band;
+
 mod bss;
 pub use bss::*;
 
+mod bss_table;
+pub use bss_table::*;
+
+mod scan;
+pub use scan::*;
+
 mod station;
 pub use station::*;
 
+mod rate;
+pub use rate::*;
+
+mod rate_stats;
+pub use rate_stats::*;
+
+mod poller;
+pub use poller::*;
+
+mod smoothing;
+pub use smoothing::*;
+
+mod survey;
+pub use survey::*;
+
+mod interference;
+pub use interference::*;
+
+mod wiphy;
+pub use wiphy::*;
+
 mod interface;
 pub use interface::*;
 
+mod connect;
+pub use connect::*;
+
+mod ap;
+pub use ap::*;
+
+mod power;
+pub use power::*;
+
+mod txq;
+pub use txq::*;
+
+mod monitor;
+pub use monitor::*;
+
+mod reason;
+pub use reason::*;
+
+mod event;
+pub use event::*;
+
+mod quality;
+pub use quality::*;
+
+mod parse;
+pub use parse::*;
+
 mod socket;
 pub use socket::*;
 
+mod shared_socket;
+pub use shared_socket::*;
+
+mod client;
+pub use client::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod wait;
+pub use wait::*;
+
+mod queue;
+pub use queue::*;
+
+#[cfg(feature = "rtnetlink")]
+mod link;
+
 #[cfg(feature = "async")]
 mod async_socket;
 #[cfg(feature = "async")]
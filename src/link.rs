@@ -0,0 +1,275 @@
+use crate::interface::{Interface, LinkState};
+use crate::socket::flatten_error;
+#[cfg(feature = "async")]
+use crate::AsyncSocket;
+use crate::Socket;
+
+#[cfg(feature = "async")]
+use neli::consts::nl::Nlmsg;
+use neli::consts::nl::{NlmF, NlmFFlags};
+use neli::consts::rtnl::{Arphrd, Iff, IffFlags, Ifla, RtAddrFamily, Rtm};
+use neli::consts::socket::NlFamily;
+use neli::err::NlError;
+use neli::nl::{NlPayload, Nlmsghdr};
+use neli::rtnl::{Ifinfomsg, Rtattr};
+#[cfg(feature = "async")]
+use neli::socket::tokio;
+use neli::socket::NlSocketHandle;
+use neli::types::{Buffer, RtBuffer};
+
+#[cfg(feature = "async")]
+use std::io;
+
+impl From<&Ifinfomsg> for LinkState {
+    fn from(msg: &Ifinfomsg) -> Self {
+        Self {
+            up: msg.ifi_flags.contains(&Iff::Up),
+            carrier: msg.ifi_flags.contains(&Iff::LowerUp),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rtnetlink")))]
+impl Socket {
+    /// Get the link state of a network interface
+    pub fn get_link_state(&mut self, interface_index: i32) -> Result<LinkState, NlError> {
+        let links = self.link()?.request(
+            Rtm::Getlink,
+            &[NlmF::Request],
+            link_msg(
+                interface_index,
+                IffFlags::empty(),
+                IffFlags::empty(),
+                RtBuffer::new(),
+            ),
+        )?;
+        first_link_state(&links)
+    }
+
+    /// Bring a network interface up or down, like `ip link set <dev> up|down`
+    ///
+    /// Most drivers only allow changing the interface type while it is down, and only accept
+    /// commands such as [`Socket::start_ap`] or [`Socket::set_frequency`] while it is up.
+    pub fn set_interface_up(&mut self, interface_index: i32, up: bool) -> Result<(), NlError> {
+        let flags = if up {
+            IffFlags::new(&[Iff::Up])
+        } else {
+            IffFlags::empty()
+        };
+        self.link()?
+            .request(
+                Rtm::Newlink,
+                &[NlmF::Request, NlmF::Ack],
+                link_msg(
+                    interface_index,
+                    flags,
+                    IffFlags::new(&[Iff::Up]),
+                    RtBuffer::new(),
+                ),
+            )
+            .map(drop)
+    }
+
+    /// Change the MAC address of a network interface, like `ip link set <dev> address <mac>`
+    ///
+    /// Most drivers refuse to change the address of an interface which is up, bring it down
+    /// first with [`Socket::set_interface_up`].
+    pub fn set_interface_mac(&mut self, interface_index: i32, mac: &[u8]) -> Result<(), NlError> {
+        if mac.len() != 6 {
+            return Err(NlError::msg("A MAC address must be 6 bytes long"));
+        }
+        let mut attrs = RtBuffer::new();
+        attrs.push(Rtattr::new(None, Ifla::Address, Buffer::from(mac))?);
+        self.link()?
+            .request(
+                Rtm::Newlink,
+                &[NlmF::Request, NlmF::Ack],
+                link_msg(interface_index, IffFlags::empty(), IffFlags::empty(), attrs),
+            )
+            .map(drop)
+    }
+
+    /// Fill in the link state of the interfaces with a single dump of all links
+    pub(crate) fn fill_link_states(&mut self, interfaces: &mut [Interface]) -> Result<(), NlError> {
+        let links =
+            self.link()?
+                .request(Rtm::Getlink, &[NlmF::Request, NlmF::Dump], dump_links_msg())?;
+        set_link_states(&links, interfaces);
+        Ok(())
+    }
+
+    /// The rtnetlink socket of this socket, opened on first use
+    fn link(&mut self) -> Result<&mut LinkSocket, NlError> {
+        let link = match self.link.take() {
+            Some(link) => link,
+            None => LinkSocket::connect()?,
+        };
+        Ok(self.link.insert(link))
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "async", feature = "rtnetlink"))))]
+impl AsyncSocket {
+    /// Get the link state of a network interface, see [`Socket::get_link_state`]
+    pub async fn get_link_state(&mut self, interface_index: i32) -> Result<LinkState, NlError> {
+        let links = self
+            .route_request(
+                Rtm::Getlink,
+                &[NlmF::Request, NlmF::Ack],
+                link_msg(
+                    interface_index,
+                    IffFlags::empty(),
+                    IffFlags::empty(),
+                    RtBuffer::new(),
+                ),
+            )
+            .await?;
+        first_link_state(&links)
+    }
+
+    /// Fill in the link state of the interfaces with a single dump of all links
+    pub(crate) async fn fill_link_states(
+        &mut self,
+        interfaces: &mut [Interface],
+    ) -> Result<(), NlError> {
+        let links = self
+            .route_request(Rtm::Getlink, &[NlmF::Request, NlmF::Dump], dump_links_msg())
+            .await?;
+        set_link_states(&links, interfaces);
+        Ok(())
+    }
+
+    /// Send a link request on the rtnetlink socket of this socket, opened on first use
+    async fn route_request(
+        &mut self,
+        cmd: Rtm,
+        flags: &[NlmF],
+        ifinfomsg: Ifinfomsg,
+    ) -> Result<Vec<Ifinfomsg>, NlError> {
+        let sock = match self.link.take() {
+            Some(sock) => sock,
+            None => tokio::NlSocket::new(NlSocketHandle::connect(NlFamily::Route, None, &[])?)?,
+        };
+        let sock = self.link.insert(sock);
+        sock.send(&link_request(cmd, flags, ifinfomsg)).await?;
+
+        let mut buf = Vec::new();
+        let mut links = Vec::new();
+        loop {
+            for response in sock.recv::<Rtm, Ifinfomsg>(&mut buf).await? {
+                match response.nl_payload {
+                    NlPayload::Payload(msg) => links.push(msg),
+                    NlPayload::Err(err) => {
+                        return Err(io::Error::from_raw_os_error(-err.error).into())
+                    }
+                    NlPayload::Ack(_) => return Ok(links),
+                    _ if u16::from(response.nl_type) == u16::from(Nlmsg::Done) => return Ok(links),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// An rtnetlink socket for the link requests of a [`Socket`]
+pub(crate) struct LinkSocket {
+    sock: NlSocketHandle,
+}
+
+impl LinkSocket {
+    fn connect() -> Result<Self, NlError> {
+        Ok(Self {
+            sock: NlSocketHandle::connect(NlFamily::Route, None, &[])?,
+        })
+    }
+
+    /// Send a link request and collect the links of the reply
+    fn request(
+        &mut self,
+        cmd: Rtm,
+        flags: &[NlmF],
+        ifinfomsg: Ifinfomsg,
+    ) -> Result<Vec<Ifinfomsg>, NlError> {
+        self.sock.send(link_request(cmd, flags, ifinfomsg))?;
+
+        let mut links = Vec::new();
+        for response in self.sock.iter::<Rtm, Ifinfomsg>(false) {
+            if let NlPayload::Payload(msg) = response.map_err(flatten_error)?.nl_payload {
+                links.push(msg);
+            }
+        }
+        Ok(links)
+    }
+}
+
+fn first_link_state(links: &[Ifinfomsg]) -> Result<LinkState, NlError> {
+    match links.first() {
+        Some(link) => Ok(link.into()),
+        None => Err(NlError::msg("No link was returned by the kernel")),
+    }
+}
+
+fn set_link_states(links: &[Ifinfomsg], interfaces: &mut [Interface]) {
+    for interface in interfaces {
+        interface.link_state = links
+            .iter()
+            .find(|link| Some(link.ifi_index) == interface.index)
+            .map(LinkState::from);
+    }
+}
+
+fn dump_links_msg() -> Ifinfomsg {
+    link_msg(0, IffFlags::empty(), IffFlags::empty(), RtBuffer::new())
+}
+
+/// Build a link message for an interface, `ifi_change` masks the flags which are changed
+fn link_msg(
+    interface_index: i32,
+    ifi_flags: IffFlags,
+    ifi_change: IffFlags,
+    attrs: RtBuffer<Ifla, Buffer>,
+) -> Ifinfomsg {
+    Ifinfomsg::new(
+        RtAddrFamily::Unspecified,
+        Arphrd::None,
+        interface_index,
+        ifi_flags,
+        ifi_change,
+        attrs,
+    )
+}
+
+fn link_request(cmd: Rtm, flags: &[NlmF], ifinfomsg: Ifinfomsg) -> Nlmsghdr<Rtm, Ifinfomsg> {
+    let len = None;
+    let nl_type = cmd;
+    let flags = NlmFFlags::new(flags);
+    let seq = None;
+    let pid = None;
+    let payload = NlPayload::Payload(ifinfomsg);
+    Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
+}
+
+#[cfg(test)]
+mod test_link {
+    use super::*;
+
+    #[test]
+    fn test_link_state() {
+        let msg = Ifinfomsg::new(
+            RtAddrFamily::Unspecified,
+            Arphrd::Ether,
+            3,
+            IffFlags::new(&[Iff::Up, Iff::Broadcast]),
+            IffFlags::empty(),
+            RtBuffer::new(),
+        );
+        assert_eq!(
+            LinkState::from(&msg),
+            LinkState {
+                up: true,
+                carrier: false
+            }
+        );
+    }
+}
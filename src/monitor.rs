@@ -0,0 +1,333 @@
+use crate::attr::{Nl80211Attr, Nl80211Iftype, Nl80211MntrFlags};
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+
+/// Radiotap flag: the frame includes the FCS
+pub const RADIOTAP_F_FCS: u8 = 0x10;
+/// Radiotap TX flag: don't wait for an acknowledgement of the frame
+pub const RADIOTAP_F_TX_NOACK: u16 = 0x0008;
+/// Radiotap TX flag: the frame already has a sequence number, which must be kept
+pub const RADIOTAP_F_TX_NOSEQNO: u16 = 0x0010;
+
+const RADIOTAP_FLAGS: u32 = 1;
+const RADIOTAP_RATE: u32 = 2;
+const RADIOTAP_TX_FLAGS: u32 = 15;
+const RADIOTAP_DATA_RETRIES: u32 = 17;
+const RADIOTAP_MCS: u32 = 19;
+
+/// HT rate of an injected frame
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RadiotapMcs {
+    /// MCS index
+    pub index: u8,
+    /// Use a 40 MHz channel instead of 20 MHz
+    pub bandwidth_40: bool,
+    /// Use the short guard interval
+    pub short_gi: bool,
+}
+
+/// Radiotap header to prepend to frames injected on a monitor interface, see
+/// [`InjectionSocket`]
+///
+/// Unset fields are left to the driver.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RadiotapHeader {
+    /// Frame flags, e.g. [`RADIOTAP_F_FCS`]
+    pub flags: Option<u8>,
+    /// Legacy rate (500 kbit/s)
+    pub rate: Option<u8>,
+    /// Transmission flags, e.g. [`RADIOTAP_F_TX_NOACK`]
+    pub tx_flags: Option<u16>,
+    /// Number of retries
+    pub data_retries: Option<u8>,
+    /// HT rate, instead of a legacy rate
+    pub mcs: Option<RadiotapMcs>,
+}
+
+impl RadiotapHeader {
+    /// Serialize the header, fields are aligned to their size as required by radiotap
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut present = 0u32;
+        let mut fields = vec![0u8; 8];
+        let mut push = |bit: u32, align: usize, data: &[u8]| {
+            present |= 1 << bit;
+            while !fields.len().is_multiple_of(align) {
+                fields.push(0);
+            }
+            fields.extend_from_slice(data);
+        };
+        if let Some(flags) = self.flags {
+            push(RADIOTAP_FLAGS, 1, &[flags]);
+        }
+        if let Some(rate) = self.rate {
+            push(RADIOTAP_RATE, 1, &[rate]);
+        }
+        if let Some(tx_flags) = self.tx_flags {
+            push(RADIOTAP_TX_FLAGS, 2, &tx_flags.to_le_bytes());
+        }
+        if let Some(retries) = self.data_retries {
+            push(RADIOTAP_DATA_RETRIES, 1, &[retries]);
+        }
+        if let Some(mcs) = self.mcs {
+            const KNOWN_BW_MCS_GI: u8 = 0x07;
+            let flags = u8::from(mcs.bandwidth_40) | if mcs.short_gi { 0x04 } else { 0 };
+            push(RADIOTAP_MCS, 1, &[KNOWN_BW_MCS_GI, flags, mcs.index]);
+        }
+
+        let len = fields.len() as u16;
+        fields[2..4].copy_from_slice(&len.to_le_bytes());
+        fields[4..8].copy_from_slice(&present.to_le_bytes());
+        fields
+    }
+}
+
+/// A raw packet socket bound to a monitor interface, to inject 802.11 frames
+///
+/// The interface has to be up. Creating the socket requires `CAP_NET_RAW`.
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{InjectionSocket, Nl80211MntrFlags, RadiotapHeader, Socket};
+///
+/// let mut socket = Socket::connect()?;
+/// let monitor =
+///     socket.new_monitor_interface(0, "mon0", &[Nl80211MntrFlags::MntrFlagActive], true)?;
+/// // Bring mon0 up, e.g. with `Socket::set_interface_up` and the `rtnetlink` feature
+/// let injection = InjectionSocket::open(monitor.index.unwrap())?;
+/// let radiotap = RadiotapHeader {
+///     rate: Some(2),
+///     ..Default::default()
+/// };
+/// # let frame = [0u8; 24];
+/// injection.send(&radiotap, &frame)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct InjectionSocket {
+    fd: OwnedFd,
+}
+
+impl InjectionSocket {
+    /// Open a packet socket on a monitor interface
+    pub fn open(interface_index: i32) -> io::Result<Self> {
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                protocol.into(),
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = libc::AF_PACKET as u16;
+        addr.sll_protocol = protocol;
+        addr.sll_ifindex = interface_index;
+        let res = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { fd })
+    }
+
+    /// Inject a frame, starting with its 802.11 header, behind a radiotap header
+    pub fn send(&self, radiotap: &RadiotapHeader, frame: &[u8]) -> io::Result<()> {
+        let mut packet = radiotap.to_bytes();
+        packet.extend_from_slice(frame);
+        let sent = unsafe {
+            libc::send(
+                self.fd.as_raw_fd(),
+                packet.as_ptr() as *const libc::c_void,
+                packet.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl AsRawFd for InjectionSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+pub(crate) fn new_interface_attrs(
+    phy: u32,
+    name: &str,
+    iftype: Nl80211Iftype,
+    socket_owner: bool,
+    monitor_flags: &[Nl80211MntrFlags],
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfname, name)?);
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIftype,
+        u32::from(u16::from(iftype)),
+    )?);
+    if !monitor_flags.is_empty() {
+        let mut flags = Nlattr::new(false, false, Nl80211Attr::AttrMntrFlags, Buffer::new())?;
+        for flag in monitor_flags {
+            flags.add_nested_attribute(&Nlattr::new(false, false, *flag, ())?)?;
+        }
+        attrs.push(flags);
+    }
+    if socket_owner {
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrSocketOwner, ())?);
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn frame_attrs(
+    interface_index: i32,
+    frequency: Option<u32>,
+    frame: &[u8],
+    wait_for_ack: bool,
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIfindex,
+        interface_index,
+    )?);
+    if let Some(frequency) = frequency {
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyFreq,
+            frequency,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrOffchannelTxOk,
+            (),
+        )?);
+    }
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrFrame, frame)?);
+    if !wait_for_ack {
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrDontWaitForAck,
+            (),
+        )?);
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn remain_on_channel_attrs(
+    interface_index: i32,
+    frequency: u32,
+    duration: u32,
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIfindex,
+        interface_index,
+    )?);
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrWiphyFreq,
+        frequency,
+    )?);
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrDuration,
+        duration,
+    )?);
+    Ok(attrs)
+}
+
+pub(crate) fn cookie_attrs(
+    interface_index: i32,
+    cookie: u64,
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIfindex,
+        interface_index,
+    )?);
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrCookie, cookie)?);
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod test_monitor {
+    use super::*;
+
+    #[test]
+    fn test_radiotap() {
+        assert_eq!(
+            RadiotapHeader::default().to_bytes(),
+            vec![0, 0, 8, 0, 0, 0, 0, 0]
+        );
+
+        let header = RadiotapHeader {
+            rate: Some(2),
+            tx_flags: Some(RADIOTAP_F_TX_NOACK),
+            mcs: Some(RadiotapMcs {
+                index: 7,
+                bandwidth_40: true,
+                short_gi: true,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            header.to_bytes(),
+            vec![0, 0, 15, 0, 0x04, 0x80, 0x08, 0x00, 2, 0, 8, 0, 7, 5, 7]
+        );
+    }
+
+    #[test]
+    fn test_monitor_flags() {
+        let attrs = new_interface_attrs(
+            0,
+            "mon0",
+            Nl80211Iftype::IftypeMonitor,
+            false,
+            &[Nl80211MntrFlags::MntrFlagActive],
+        )
+        .unwrap();
+        let attrs = attrs.get_attr_handle();
+        let flags = attrs
+            .get_attribute(Nl80211Attr::AttrMntrFlags)
+            .unwrap()
+            .get_attr_handle::<Nl80211MntrFlags>()
+            .unwrap();
+        assert!(flags
+            .get_attribute(Nl80211MntrFlags::MntrFlagActive)
+            .is_some());
+        assert!(attrs.get_attribute(Nl80211Attr::AttrSocketOwner).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::attr::{Attrs, Nl80211Attr};
+use crate::cmd::Nl80211Cmd;
+use crate::socket::copy_attr;
+use crate::{Bss, Interface, Station, Wiphy};
+
+use neli::err::DeError;
+use neli::genl::Genlmsghdr;
+use neli::types::GenlBuffer;
+use neli::FromBytesWithInput;
+
+use std::fmt;
+use std::io::Cursor;
+use std::slice;
+
+/// An attribute skipped because it failed to be parsed, see
+/// [`SocketBuilder::lenient`](crate::SocketBuilder::lenient)
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// Command of the message containing the attribute
+    pub cmd: Nl80211Cmd,
+    /// Type of the attribute
+    pub attr: Nl80211Attr,
+    /// Why the attribute couldn't be parsed
+    pub error: DeError,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "skipped attribute {:?} of {:?}: {}",
+            self.attr, self.cmd, self.error
+        )
+    }
+}
+
+/// Parse a raw nl80211 message, starting with the generic netlink header
+///
+/// Netlink captures (e.g. from an `nlmon` interface) also contain the 16 bytes netlink header,
+/// which has to be skipped.
+pub fn parse_message(bytes: &[u8]) -> Result<Genlmsghdr<Nl80211Cmd, Nl80211Attr>, DeError> {
+    const GENL_HEADER_LEN: usize = 4;
+
+    if bytes.len() < GENL_HEADER_LEN {
+        return Err(DeError::UnexpectedEOB);
+    }
+    Genlmsghdr::from_bytes_with_input(&mut Cursor::new(bytes), bytes.len())
+}
+
+fn parse<T>(bytes: &[u8]) -> Result<T, DeError>
+where
+    T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+{
+    parse_message(bytes)?.get_attr_handle().try_into()
+}
+
+/// Parse the attributes of a message, skipping the ones which fail to be parsed if `lenient`
+///
+/// The failing attributes are found by parsing each attribute on its own, which works as long as
+/// the fields of `T` are filled independently from each other. Only top-level attributes are
+/// skipped, an attribute with a malformed nested attribute is skipped as a whole.
+pub(crate) fn parse_attrs<T>(
+    msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>,
+    lenient: bool,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<T, DeError>
+where
+    T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+{
+    let handle = msg.get_attr_handle();
+    let attrs = handle.get_attrs();
+    let err = match T::try_from(Attrs::new_borrowed(attrs)) {
+        Ok(res) => return Ok(res),
+        Err(err) if !lenient => return Err(err),
+        Err(err) => err,
+    };
+
+    let mut valid = GenlBuffer::new();
+    for attr in attrs {
+        match T::try_from(Attrs::new_borrowed(slice::from_ref(attr))) {
+            Ok(_) => valid.push(copy_attr(attr)),
+            Err(error) => warnings.push(ParseWarning {
+                cmd: msg.cmd,
+                attr: attr.nla_type.nla_type,
+                error,
+            }),
+        }
+    }
+    T::try_from(Attrs::new(valid)).map_err(|_| err)
+}
+
+/// Parse an [`Interface`] from a raw `NL80211_CMD_NEW_INTERFACE` message, see [`parse_message`]
+pub fn parse_interface(bytes: &[u8]) -> Result<Interface, DeError> {
+    parse(bytes)
+}
+
+/// Parse a [`Station`] from a raw `NL80211_CMD_NEW_STATION` message, see [`parse_message`]
+pub fn parse_station(bytes: &[u8]) -> Result<Station, DeError> {
+    parse(bytes)
+}
+
+/// Parse a [`Bss`] from a raw `NL80211_CMD_NEW_SCAN_RESULTS` message, see [`parse_message`]
+pub fn parse_bss(bytes: &[u8]) -> Result<Bss, DeError> {
+    parse(bytes)
+}
+
+/// Parse a [`Wiphy`] from a raw `NL80211_CMD_NEW_WIPHY` message, see [`parse_message`]
+///
+/// Split wiphy dumps spread a wiphy over several messages, each of them only fills some fields.
+pub fn parse_wiphy(bytes: &[u8]) -> Result<Wiphy, DeError> {
+    parse(bytes)
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+    use crate::attr::Nl80211StaInfo;
+    use neli::genl::Nlattr;
+    use neli::types::Buffer;
+    use neli::ToBytes;
+
+    #[test]
+    fn test_parse_interface() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfindex, 3i32).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, 1u32).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphyFreq, 2412u32).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNewInterface, 1, attrs);
+        let mut bytes = Cursor::new(Vec::new());
+        msg.to_bytes(&mut bytes).unwrap();
+        let bytes = bytes.into_inner();
+
+        assert_eq!(
+            parse_message(&bytes).unwrap().cmd,
+            Nl80211Cmd::CmdNewInterface
+        );
+        let interface = parse_interface(&bytes).unwrap();
+        assert_eq!(interface.index, Some(3));
+        assert_eq!(interface.phy, Some(1));
+        assert_eq!(interface.frequency, Some(2412));
+        assert_eq!(parse_wiphy(&bytes).unwrap().index, Some(1));
+
+        assert!(parse_interface(&bytes[..2]).is_err());
+        assert!(parse_interface(&bytes[..10]).is_err());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfindex, 3i32).unwrap());
+        // Truncated frequency
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphyFreq, 2412u16).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, 1u32).unwrap());
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNewInterface, 1, attrs);
+
+        let mut warnings = Vec::new();
+        assert!(parse_attrs::<Interface>(&msg, false, &mut warnings).is_err());
+        assert!(warnings.is_empty());
+
+        let interface: Interface = parse_attrs(&msg, true, &mut warnings).unwrap();
+        assert_eq!(interface.index, Some(3));
+        assert_eq!(interface.phy, Some(1));
+        assert_eq!(interface.frequency, None);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].cmd, Nl80211Cmd::CmdNewInterface);
+        assert_eq!(warnings[0].attr, Nl80211Attr::AttrWiphyFreq);
+    }
+
+    #[test]
+    fn test_parse_lenient_nested() {
+        let mut info = Nlattr::new(true, false, Nl80211Attr::AttrStaInfo, Buffer::new()).unwrap();
+        info.add_nested_attribute(
+            &Nlattr::new(false, false, Nl80211StaInfo::StaInfoSignal, -50i8).unwrap(),
+        )
+        .unwrap();
+        // Truncated counter
+        info.add_nested_attribute(
+            &Nlattr::new(false, false, Nl80211StaInfo::StaInfoRxBytes, 1u16).unwrap(),
+        )
+        .unwrap();
+        let mut attrs = GenlBuffer::new();
+        attrs.push(
+            Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrMac,
+                &[2u8, 0, 0, 0, 0, 1][..],
+            )
+            .unwrap(),
+        );
+        attrs.push(info);
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNewStation, 1, attrs);
+
+        let mut warnings = Vec::new();
+        assert!(parse_attrs::<Station>(&msg, false, &mut warnings).is_err());
+
+        // The valid signal is skipped along with the rest of the station information
+        let station: Station = parse_attrs(&msg, true, &mut warnings).unwrap();
+        assert_eq!(station.bssid, Some(vec![2, 0, 0, 0, 0, 1]));
+        assert_eq!(station.signal, None);
+        assert_eq!(station.rx_bytes, None);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].attr, Nl80211Attr::AttrStaInfo);
+    }
+}
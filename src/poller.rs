@@ -0,0 +1,218 @@
+use crate::station::Station;
+use crate::Socket;
+
+use neli::err::NlError;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Counter deltas of a station between two samples of a [`StationPoller`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StationDelta {
+    /// MAC address of the station
+    pub mac: Option<Vec<u8>>,
+    /// Time elapsed between the two samples
+    pub elapsed: Duration,
+    /// Bytes received from the station
+    pub rx_bytes: Option<u64>,
+    /// Bytes transmitted to the station
+    pub tx_bytes: Option<u64>,
+    /// Packets received from the station
+    pub rx_packets: Option<u64>,
+    /// Packets transmitted to the station
+    pub tx_packets: Option<u64>,
+    /// Retries of packets transmitted to the station
+    pub tx_retries: Option<u64>,
+    /// Packets which couldn't be transmitted to the station
+    pub tx_failed: Option<u64>,
+}
+
+impl StationDelta {
+    /// Compute the deltas between two samples of the same station, `None` if the counters went
+    /// backwards because the station reconnected
+    pub fn between(previous: &Station, current: &Station, elapsed: Duration) -> Option<Self> {
+        if current.connected_time < previous.connected_time {
+            return None;
+        }
+        Some(Self {
+            mac: current.bssid.clone(),
+            elapsed,
+            rx_bytes: delta_bytes(previous.rx_bytes, current.rx_bytes),
+            tx_bytes: delta_bytes(previous.tx_bytes, current.tx_bytes),
+            rx_packets: delta_u32(previous.rx_packets, current.rx_packets),
+            tx_packets: delta_u32(previous.tx_packets, current.tx_packets),
+            tx_retries: delta_u32(previous.tx_retries, current.tx_retries),
+            tx_failed: delta_u32(previous.tx_failed, current.tx_failed),
+        })
+    }
+
+    /// Reception throughput (bit/s)
+    pub fn rx_throughput(&self) -> Option<f64> {
+        self.rate(self.rx_bytes? * 8)
+    }
+
+    /// Transmission throughput (bit/s)
+    pub fn tx_throughput(&self) -> Option<f64> {
+        self.rate(self.tx_bytes? * 8)
+    }
+
+    /// Ratio of retries to transmitted packets
+    pub fn retry_rate(&self) -> Option<f64> {
+        ratio(self.tx_retries?, self.tx_packets?)
+    }
+
+    /// Ratio of failed packets to transmission attempts
+    pub fn loss_rate(&self) -> Option<f64> {
+        let failed = self.tx_failed?;
+        ratio(failed, self.tx_packets? + failed)
+    }
+
+    fn rate(&self, count: u64) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (secs > 0.0).then(|| count as f64 / secs)
+    }
+}
+
+fn ratio(count: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+/// Delta of a 32 bits counter, which may have wrapped around
+fn delta_u32(previous: Option<u32>, current: Option<u32>) -> Option<u64> {
+    Some(current?.wrapping_sub(previous?).into())
+}
+
+/// Delta of a byte counter, drivers which don't report 64 bits counters wrap around at 2^32
+fn delta_bytes(previous: Option<u64>, current: Option<u64>) -> Option<u64> {
+    let (previous, current) = (previous?, current?);
+    if current >= previous {
+        Some(current - previous)
+    } else if previous <= u64::from(u32::MAX) {
+        Some(current + (1 << 32) - previous)
+    } else {
+        None
+    }
+}
+
+/// Sample the station of an interface (its access point in station mode) at a fixed interval
+/// and compute the counter deltas
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{Socket, StationPoller};
+/// use std::time::Duration;
+///
+/// let mut socket = Socket::connect()?;
+/// let mut poller = StationPoller::new(3, Duration::from_secs(1));
+/// loop {
+///     if let Some(delta) = poller.poll(&mut socket)? {
+///         println!("rx {:?} bit/s", delta.rx_throughput());
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct StationPoller {
+    interface_index: i32,
+    interval: Duration,
+    last: Option<(Instant, Station)>,
+}
+
+impl StationPoller {
+    /// Create a poller for the station of an interface
+    pub fn new(interface_index: i32, interval: Duration) -> Self {
+        Self {
+            interface_index,
+            interval,
+            last: None,
+        }
+    }
+
+    /// Wait until the interval has elapsed since the previous sample, then sample the station
+    ///
+    /// The first call returns immediately without a delta, as does a call after the station
+    /// changed or reconnected.
+    pub fn poll(&mut self, socket: &mut Socket) -> Result<Option<StationDelta>, NlError> {
+        if let Some((last, _)) = &self.last {
+            if let Some(remaining) = self.interval.checked_sub(last.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+        let station = socket.get_station_info(self.interface_index)?;
+        Ok(self.update(station, Instant::now()))
+    }
+
+    /// Record a sample taken at `now`, e.g. from an async socket, and compute the deltas since
+    /// the previous one
+    pub fn update(&mut self, station: Station, now: Instant) -> Option<StationDelta> {
+        let delta = match &self.last {
+            Some((last, previous)) if previous.bssid == station.bssid => {
+                StationDelta::between(previous, &station, now.saturating_duration_since(*last))
+            }
+            _ => None,
+        };
+        self.last = Some((now, station));
+        delta
+    }
+}
+
+#[cfg(test)]
+mod test_poller {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let start = Instant::now();
+        let mut poller = StationPoller::new(3, Duration::from_secs(1));
+        let station = Station {
+            bssid: Some(vec![2, 0, 0, 0, 0, 1]),
+            connected_time: Some(10),
+            rx_bytes: Some(u64::from(u32::MAX) - 999),
+            tx_bytes: Some(1_000),
+            tx_packets: Some(u32::MAX - 9),
+            tx_retries: Some(0),
+            tx_failed: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(poller.update(station.clone(), start), None);
+
+        let station = Station {
+            connected_time: Some(12),
+            rx_bytes: Some(1_000),
+            tx_bytes: Some(501_000),
+            tx_packets: Some(90),
+            tx_retries: Some(10),
+            tx_failed: Some(25),
+            ..station
+        };
+        let delta = poller
+            .update(station, start + Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(delta.rx_bytes, Some(2_000));
+        assert_eq!(delta.tx_packets, Some(100));
+        assert_eq!(delta.rx_throughput(), Some(8_000.0));
+        assert_eq!(delta.tx_throughput(), Some(2_000_000.0));
+        assert_eq!(delta.retry_rate(), Some(0.1));
+        assert_eq!(delta.loss_rate(), Some(0.2));
+        assert_eq!(delta.rx_packets, None);
+    }
+
+    #[test]
+    fn test_reconnect() {
+        let previous = Station {
+            connected_time: Some(100),
+            rx_bytes: Some(1 << 40),
+            ..Default::default()
+        };
+        let current = Station {
+            connected_time: Some(1),
+            rx_bytes: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            StationDelta::between(&previous, &current, Duration::from_secs(1)),
+            None
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::attr::{Nl80211Attr, Nl80211TxPowerSetting};
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+use std::fmt;
+
+/// Power level in mBm (100 * dBm)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mbm(pub i32);
+
+impl Mbm {
+    /// Power level from dBm
+    pub fn from_dbm(dbm: i32) -> Self {
+        Self(dbm * 100)
+    }
+
+    /// Power level in dBm
+    pub fn dbm(&self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    /// Power level in mW
+    pub fn milliwatts(&self) -> f64 {
+        10f64.powf(self.dbm() / 10.0)
+    }
+}
+
+/// Formats the power like `iw`, e.g. "20.00 dBm"
+impl fmt::Display for Mbm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} dBm", self.dbm())
+    }
+}
+
+/// Transmit power setting, see [`Socket::set_tx_power`](crate::Socket::set_tx_power)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxPower {
+    /// Let the driver pick the power level
+    Automatic,
+    /// Limit the power level to at most this value
+    Limited(Mbm),
+    /// Use this power level
+    Fixed(Mbm),
+}
+
+pub(crate) fn tx_power_attrs(
+    interface_index: i32,
+    power: TxPower,
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let (setting, level) = match power {
+        TxPower::Automatic => (Nl80211TxPowerSetting::TxPowerAutomatic, None),
+        TxPower::Limited(level) => (Nl80211TxPowerSetting::TxPowerLimited, Some(level)),
+        TxPower::Fixed(level) => (Nl80211TxPowerSetting::TxPowerFixed, Some(level)),
+    };
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIfindex,
+        interface_index,
+    )?);
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrWiphyTxPowerSetting,
+        u32::from(u16::from(setting)),
+    )?);
+    if let Some(level) = level {
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyTxPowerLevel,
+            level.0,
+        )?);
+    }
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod test_power {
+    use super::*;
+
+    #[test]
+    fn test_mbm() {
+        let power = Mbm::from_dbm(20);
+        assert_eq!(power, Mbm(2000));
+        assert_eq!(power.to_string(), "20.00 dBm");
+        assert_eq!(Mbm(-150).to_string(), "-1.50 dBm");
+        assert!((power.milliwatts() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tx_power_attrs() {
+        let attrs = tx_power_attrs(3, TxPower::Fixed(Mbm(1500))).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrWiphyTxPowerSetting)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<i32>(Nl80211Attr::AttrWiphyTxPowerLevel)
+                .unwrap(),
+            1500
+        );
+
+        let attrs = tx_power_attrs(3, TxPower::Automatic).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert!(attrs
+            .get_attribute(Nl80211Attr::AttrWiphyTxPowerLevel)
+            .is_none());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::event::Event;
+
+/// Counts the signs of a degrading connection reported by the events of an interface, to act
+/// before the interface gets disconnected
+///
+/// The counters are reset when the interface connects or disconnects.
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{QualityCounters, Socket};
+///
+/// let mut socket = Socket::connect()?;
+/// socket.subscribe("mlme")?;
+/// let mut counters = QualityCounters::default();
+/// loop {
+///     let event = socket.next_event()?;
+///     if counters.update(3, &event) && counters.beacon_losses > 2 {
+///         println!("The access point is fading away, look for another one");
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QualityCounters {
+    /// Beacon loss events
+    pub beacon_losses: u32,
+    /// Low acknowledgement events
+    pub low_acks: u32,
+    /// Frames not acknowledged, summed over the low acknowledgement events
+    pub lost_packets: u64,
+}
+
+impl QualityCounters {
+    /// Count an event of the interface, returning whether it was a beacon loss or low
+    /// acknowledgement event
+    ///
+    /// Events of other interfaces are ignored.
+    pub fn update(&mut self, interface_index: i32, event: &Event) -> bool {
+        let index = match event {
+            Event::BeaconLoss(notification) | Event::LowAck(notification) => notification.index,
+            Event::Connect(result) => result.index,
+            Event::Disconnect(disconnect) => disconnect.index,
+            _ => return false,
+        };
+        if index != Some(interface_index) {
+            return false;
+        }
+        match event {
+            Event::BeaconLoss(_) => self.beacon_losses += 1,
+            Event::LowAck(notification) => {
+                self.low_acks += 1;
+                self.lost_packets += u64::from(notification.lost_packets.unwrap_or_default());
+            }
+            _ => {
+                self.reset();
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether any beacon loss or low acknowledgement event was counted
+    pub fn is_degraded(&self) -> bool {
+        self.beacon_losses > 0 || self.low_acks > 0
+    }
+
+    /// Reset the counters, e.g. after roaming
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod test_quality {
+    use super::*;
+    use crate::event::{CqmNotification, Disconnect};
+
+    #[test]
+    fn test_update() {
+        let mut counters = QualityCounters::default();
+        let beacon_loss = Event::BeaconLoss(CqmNotification {
+            index: Some(3),
+            beacon_loss: true,
+            ..Default::default()
+        });
+        let low_ack = Event::LowAck(CqmNotification {
+            index: Some(3),
+            lost_packets: Some(50),
+            ..Default::default()
+        });
+
+        assert!(counters.update(3, &beacon_loss));
+        assert!(counters.update(3, &low_ack));
+        assert!(counters.update(3, &low_ack));
+        assert!(!counters.update(4, &beacon_loss));
+        assert_eq!(
+            counters,
+            QualityCounters {
+                beacon_losses: 1,
+                low_acks: 2,
+                lost_packets: 100,
+            }
+        );
+        assert!(counters.is_degraded());
+
+        let disconnect = Event::Disconnect(Disconnect {
+            index: Some(3),
+            ..Default::default()
+        });
+        assert!(!counters.update(3, &disconnect));
+        assert!(!counters.is_degraded());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::socket::timed_out;
+use crate::Socket;
+
+use neli::err::NlError;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Priority of a command submitted to a [`CommandQueue`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Background requests, such as periodic statistics
+    Low,
+    /// Regular requests
+    #[default]
+    Normal,
+    /// Requests which should skip ahead of queued ones, such as connecting
+    High,
+}
+
+/// A queued command, given the socket or the error preventing it from running
+type Command = Box<dyn FnOnce(Result<&mut Socket, NlError>) + Send>;
+
+struct Job {
+    priority: Priority,
+    id: u64,
+    timeout: Option<Duration>,
+    cancelled: Arc<AtomicBool>,
+    command: Command,
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Job {}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Higher priorities first, then first in, first out
+impl Ord for Job {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+#[derive(Default)]
+struct State {
+    jobs: BinaryHeap<Job>,
+    next_id: u64,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+/// Runs commands one at a time on a socket owned by a worker thread, so it can be shared by
+/// several threads without their requests and replies getting mixed up
+///
+/// Commands are run by priority, with at least `min_interval` between the start of two
+/// commands. Each command can have a timeout, after which it fails with
+/// [`std::io::ErrorKind::TimedOut`]; late replies to it are discarded by the following commands.
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{CommandQueue, Priority, WifiClient};
+/// use std::time::Duration;
+///
+/// let client = WifiClient::connect()?;
+/// let (queue, _events) = client.into_command_queue(Duration::from_millis(10));
+/// let interfaces = queue.run(Priority::Normal, Some(Duration::from_secs(2)), |socket| {
+///     socket.get_interfaces_info()
+/// })?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct CommandQueue {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl CommandQueue {
+    /// Start the worker thread, which takes ownership of the socket
+    pub fn spawn(socket: Socket, min_interval: Duration) -> Self {
+        let shared = Arc::new(Shared::default());
+        let worker = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || work(socket, &shared, min_interval))
+        };
+        Self {
+            shared,
+            worker: Some(worker),
+        }
+    }
+
+    /// Queue a command, its result is returned by [`Pending::wait`]
+    pub fn submit<T, F>(
+        &self,
+        priority: Priority,
+        timeout: Option<Duration>,
+        command: F,
+    ) -> Pending<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Socket) -> Result<T, NlError> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let command: Command = Box::new(move |socket| {
+            let result = socket.and_then(command).map_err(timed_out);
+            // The receiver may have been dropped, the result isn't wanted anymore then
+            let _ = sender.send(result);
+        });
+
+        let mut state = self.shared.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.jobs.push(Job {
+            priority,
+            id,
+            timeout,
+            cancelled: Arc::clone(&cancelled),
+            command,
+        });
+        self.shared.available.notify_one();
+
+        Pending {
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// Queue a command and wait for its result
+    pub fn run<T, F>(
+        &self,
+        priority: Priority,
+        timeout: Option<Duration>,
+        command: F,
+    ) -> Result<T, NlError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Socket) -> Result<T, NlError> + Send + 'static,
+    {
+        self.submit(priority, timeout, command).wait()
+    }
+}
+
+/// Commands which were already queued are still run before the worker stops
+impl Drop for CommandQueue {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().closed = true;
+        self.shared.available.notify_one();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A command submitted to a [`CommandQueue`]
+///
+/// Dropping it doesn't cancel the command.
+pub struct Pending<T> {
+    receiver: mpsc::Receiver<Result<T, NlError>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> Pending<T> {
+    /// Wait for the result of the command
+    pub fn wait(self) -> Result<T, NlError> {
+        self.receiver
+            .recv()
+            .unwrap_or_else(|_| Err(NlError::msg("The command was cancelled")))
+    }
+
+    /// Don't run the command if it is still queued, a running command is completed but its
+    /// result is discarded
+    pub fn cancel(self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+}
+
+fn work(mut socket: Socket, shared: &Shared, min_interval: Duration) {
+    let mut last_start: Option<Instant> = None;
+    loop {
+        let job = {
+            let mut state = shared.state.lock().unwrap();
+            loop {
+                if let Some(job) = state.jobs.pop() {
+                    break job;
+                }
+                if state.closed {
+                    return;
+                }
+                state = shared.available.wait(state).unwrap();
+            }
+        };
+        if job.cancelled.load(AtomicOrdering::Relaxed) {
+            continue;
+        }
+
+        if let Some(remaining) = last_start.and_then(|t| min_interval.checked_sub(t.elapsed())) {
+            thread::sleep(remaining);
+        }
+        last_start = Some(Instant::now());
+
+        match socket.set_recv_timeout(job.timeout) {
+            Ok(()) => (job.command)(Ok(&mut socket)),
+            Err(err) => (job.command)(Err(err.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_queue {
+    use super::*;
+
+    fn job(priority: Priority, id: u64) -> Job {
+        Job {
+            priority,
+            id,
+            timeout: None,
+            cancelled: Arc::default(),
+            command: Box::new(|_| ()),
+        }
+    }
+
+    #[test]
+    fn test_job_order() {
+        let mut jobs = BinaryHeap::new();
+        jobs.push(job(Priority::Normal, 0));
+        jobs.push(job(Priority::Low, 1));
+        jobs.push(job(Priority::High, 2));
+        jobs.push(job(Priority::Normal, 3));
+        jobs.push(job(Priority::High, 4));
+
+        let order: Vec<u64> = std::iter::from_fn(|| jobs.pop().map(|job| job.id)).collect();
+        assert_eq!(order, vec![2, 4, 0, 3, 1]);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::attr::{Attrs, Nl80211RateInfo, PayloadExt};
+
+use neli::err::DeError;
+
+use std::fmt;
+
+/// Bitrate information of a station, see [`Station::tx_rate`](crate::Station::tx_rate)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RateInfo {
+    /// Total bitrate (100 kbit/s)
+    pub bitrate: Option<u32>,
+    /// HT MCS index
+    pub mcs: Option<u8>,
+    /// VHT MCS index
+    pub vht_mcs: Option<u8>,
+    /// VHT number of spatial streams
+    pub vht_nss: Option<u8>,
+    /// HE MCS index
+    pub he_mcs: Option<u8>,
+    /// HE number of spatial streams
+    pub he_nss: Option<u8>,
+    /// HE guard interval, 0 for 0.8 µs, 1 for 1.6 µs and 2 for 3.2 µs
+    pub he_gi: Option<u8>,
+    /// EHT MCS index
+    pub eht_mcs: Option<u8>,
+    /// EHT number of spatial streams
+    pub eht_nss: Option<u8>,
+    /// EHT guard interval, with the same values as `he_gi`
+    pub eht_gi: Option<u8>,
+    /// Channel width (MHz), unset for 20 MHz, 80+80 MHz is reported as 160 MHz
+    pub width: Option<u16>,
+    /// Short guard interval (HT/VHT)
+    pub short_gi: bool,
+}
+
+impl RateInfo {
+    /// Total bitrate in MBit/s
+    pub fn mbps(&self) -> Option<f64> {
+        self.bitrate.map(|bitrate| f64::from(bitrate) / 10.0)
+    }
+}
+
+/// Formats the rate like `iw`, e.g. "866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2"
+impl fmt::Display for RateInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(bitrate) = self.bitrate {
+            parts.push(format!("{}.{} MBit/s", bitrate / 10, bitrate % 10));
+        }
+        for (name, value) in [
+            ("MCS", self.mcs),
+            ("VHT-MCS", self.vht_mcs),
+            ("HE-MCS", self.he_mcs),
+            ("EHT-MCS", self.eht_mcs),
+        ] {
+            if let Some(value) = value {
+                parts.push(format!("{} {}", name, value));
+            }
+        }
+        if let Some(width) = self.width {
+            parts.push(format!("{}MHz", width));
+        }
+        if self.short_gi {
+            parts.push("short GI".to_string());
+        }
+        for (name, value) in [
+            ("VHT-NSS", self.vht_nss),
+            ("HE-NSS", self.he_nss),
+            ("HE-GI", self.he_gi),
+            ("EHT-NSS", self.eht_nss),
+            ("EHT-GI", self.eht_gi),
+        ] {
+            if let Some(value) = value {
+                parts.push(format!("{} {}", name, value));
+            }
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211RateInfo>> for RateInfo {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211RateInfo>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        let mut bitrate16 = None;
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211RateInfo::RateInfoBitrate32 => res.bitrate = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoBitrate => {
+                    bitrate16 = Some(attr.get_payload_exact::<u16>()?)
+                }
+                Nl80211RateInfo::RateInfoMcs => res.mcs = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoVhtMcs => res.vht_mcs = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoVhtNss => res.vht_nss = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoHeMcs => res.he_mcs = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoHeNss => res.he_nss = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoHeGi => res.he_gi = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoEhtMcs => res.eht_mcs = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoEhtNss => res.eht_nss = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoEhtGi => res.eht_gi = Some(attr.get_payload_exact()?),
+                Nl80211RateInfo::RateInfoShortGi => res.short_gi = true,
+                Nl80211RateInfo::RateInfo1MhzWidth => res.width = Some(1),
+                Nl80211RateInfo::RateInfo2MhzWidth => res.width = Some(2),
+                Nl80211RateInfo::RateInfo4MhzWidth => res.width = Some(4),
+                Nl80211RateInfo::RateInfo5MhzWidth => res.width = Some(5),
+                Nl80211RateInfo::RateInfo8MhzWidth => res.width = Some(8),
+                Nl80211RateInfo::RateInfo10MhzWidth => res.width = Some(10),
+                Nl80211RateInfo::RateInfo16MhzWidth => res.width = Some(16),
+                Nl80211RateInfo::RateInfo40MhzWidth => res.width = Some(40),
+                Nl80211RateInfo::RateInfo80MhzWidth => res.width = Some(80),
+                Nl80211RateInfo::RateInfo80p80MhzWidth | Nl80211RateInfo::RateInfo160MhzWidth => {
+                    res.width = Some(160)
+                }
+                Nl80211RateInfo::RateInfo320MhzWidth => res.width = Some(320),
+                _ => (),
+            }
+        }
+        res.bitrate = res.bitrate.or(bitrate16.map(u32::from));
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_rate {
+    use super::*;
+    use neli::genl::Nlattr;
+    use neli::types::GenlBuffer;
+
+    #[test]
+    fn test_display() {
+        let rate = RateInfo {
+            bitrate: Some(8667),
+            vht_mcs: Some(9),
+            vht_nss: Some(2),
+            width: Some(80),
+            short_gi: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            rate.to_string(),
+            "866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2"
+        );
+        assert_eq!(rate.mbps(), Some(866.7));
+        assert_eq!(RateInfo::default().to_string(), "");
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211RateInfo::RateInfoBitrate, 1201u16).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211RateInfo::RateInfoHeMcs, 11u8).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211RateInfo::RateInfoHeNss, 2u8).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211RateInfo::RateInfoHeGi, 0u8).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211RateInfo::RateInfo160MhzWidth, ()).unwrap());
+
+        let rate: RateInfo = attrs.get_attr_handle().try_into().unwrap();
+        assert_eq!(
+            rate.to_string(),
+            "120.1 MBit/s HE-MCS 11 160MHz HE-NSS 2 HE-GI 0"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::io;
+
+/// Rate control statistics of one rate, see [`RateStats`]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RateStat {
+    /// Modulation, e.g. "CCK", "OFDM", "HT" or "VHT"
+    pub mode: String,
+    /// Channel width (MHz), only reported for HT and VHT rates
+    pub width: Option<u16>,
+    /// Short guard interval
+    pub short_gi: bool,
+    /// Number of spatial streams
+    pub streams: u8,
+    /// Name of the rate, e.g. "MCS7" for HT or "MCS9/2" for VHT
+    pub name: String,
+    /// Average throughput (Mbit/s)
+    pub throughput: f64,
+    /// Estimated probability of a successful transmission (%)
+    pub success_probability: f64,
+    /// Frames successfully transmitted at this rate since the station connected
+    pub successes: u64,
+    /// Transmission attempts at this rate since the station connected
+    pub attempts: u64,
+}
+
+/// Per-rate transmission statistics of a station, from the rate control algorithm of the
+/// driver
+///
+/// nl80211 only reports the last rate used, so the statistics come from the `rc_stats_csv`
+/// debugfs file of the minstrel_ht algorithm used by mac80211 drivers, read with
+/// `RateStats::read` with the `debugfs` feature. Drivers doing rate control in firmware usually
+/// don't provide them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RateStats {
+    /// Statistics of each rate, in the order of the driver
+    pub rates: Vec<RateStat>,
+}
+
+impl RateStats {
+    /// Parse the content of a minstrel_ht `rc_stats_csv` file
+    pub fn parse_minstrel_csv(csv: &str) -> io::Result<Self> {
+        let rates = csv
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_minstrel_line)
+            .collect::<Option<_>>()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid rc_stats_csv"))?;
+        Ok(Self { rates })
+    }
+
+    /// Read the statistics of a station from debugfs, which has to be mounted and readable
+    ///
+    /// `phy` is the name of the wiphy (e.g. "phy0"), `interface` the name of the interface and
+    /// `mac` the address of the station.
+    #[cfg(feature = "debugfs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debugfs")))]
+    pub fn read(phy: &str, interface: &str, mac: &[u8]) -> io::Result<Self> {
+        let mac = mac
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(":");
+        let path = format!(
+            "/sys/kernel/debug/ieee80211/{}/netdev:{}/stations/{}/rc_stats_csv",
+            phy, interface, mac
+        );
+        Self::parse_minstrel_csv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Total transmission attempts over all rates
+    pub fn total_attempts(&self) -> u64 {
+        self.rates.iter().map(|rate| rate.attempts).sum()
+    }
+
+    /// Share of the transmission attempts made at each rate, from 0 to 1, skipping the unused
+    /// rates
+    pub fn usage(&self) -> impl Iterator<Item = (&RateStat, f64)> {
+        let total = self.total_attempts();
+        self.rates
+            .iter()
+            .filter(|rate| rate.attempts > 0)
+            .map(move |rate| (rate, rate.attempts as f64 / total as f64))
+    }
+
+    /// The rate with the most transmission attempts
+    pub fn most_used(&self) -> Option<&RateStat> {
+        self.rates
+            .iter()
+            .filter(|rate| rate.attempts > 0)
+            .max_by_key(|rate| rate.attempts)
+    }
+}
+
+/// Parse a line of `rc_stats_csv`, whose columns are: mode and width (e.g. "HT20" or "CCK"),
+/// guard interval, streams, rate flags, name, index, airtime, maximum throughput, average
+/// throughput, success probability, retry count, last successes, last attempts, successes,
+/// attempts, followed by station totals
+fn parse_minstrel_line(line: &str) -> Option<RateStat> {
+    let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+    if columns.len() < 15 {
+        return None;
+    }
+    let (mode, width) = columns[0].split_at(
+        columns[0]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(columns[0].len()),
+    );
+    Some(RateStat {
+        mode: mode.to_string(),
+        width: if width.is_empty() {
+            None
+        } else {
+            Some(width.parse().ok()?)
+        },
+        short_gi: columns[1] == "SGI",
+        streams: columns[2].parse().ok()?,
+        name: columns[4].to_string(),
+        throughput: columns[8].parse().ok()?,
+        success_probability: columns[9].parse().ok()?,
+        successes: columns[13].parse().ok()?,
+        attempts: columns[14].parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod test_rate_stats {
+    use super::*;
+
+    #[test]
+    fn test_parse_minstrel_csv() {
+        // As written by minstrel_ht_stats_csv_dump() in mac80211's rc80211_minstrel_ht_debugfs.c
+        let csv = "\
+CCK,LP,1,, 1.0M,120,10548,0.6,0.6,100.0,0,0,0,2,2,5123,40,1.0
+HT20,LGI,1,,MCS0 ,0,1477,5.6,5.6,100.0,1,0,0,120,125,5123,40,1.0
+HT20,SGI,2,ABP,MCS15,31,132,119.2,110.4,96.3,5,10,11,4000,4200,5123,40,1.0
+VHT80,SGI,2,,MCS9/2,99,44,0.0,0.0,0.0,0,0,0,0,0,5123,40,1.0
+";
+        let stats = RateStats::parse_minstrel_csv(csv).unwrap();
+        assert_eq!(stats.rates.len(), 4);
+        let rate = &stats.rates[2];
+        assert_eq!(rate.mode, "HT");
+        assert_eq!(rate.width, Some(20));
+        assert!(rate.short_gi);
+        assert_eq!(rate.streams, 2);
+        assert_eq!(rate.name, "MCS15");
+        assert_eq!(rate.throughput, 110.4);
+        assert_eq!(rate.success_probability, 96.3);
+        assert_eq!(rate.successes, 4000);
+        assert_eq!(rate.attempts, 4200);
+
+        let cck = &stats.rates[0];
+        assert_eq!((cck.mode.as_str(), cck.width), ("CCK", None));
+        assert_eq!(cck.name, "1.0M");
+        let vht = &stats.rates[3];
+        assert_eq!((vht.mode.as_str(), vht.width), ("VHT", Some(80)));
+        assert_eq!(vht.name, "MCS9/2");
+
+        assert_eq!(stats.total_attempts(), 4327);
+        assert_eq!(stats.most_used().unwrap().name, "MCS15");
+        assert_eq!(stats.usage().count(), 3);
+
+        assert!(RateStats::parse_minstrel_csv("HT20,LGI,1").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use neli_proc_macros::neli_enum;
+
+use std::fmt;
+
+/// IEEE 802.11 reason code, sent in deauthentication and disassociation frames and reported by
+/// [`Event::Disconnect`](crate::Event::Disconnect)
+///
+/// Table 9-49 of IEEE 802.11-2020
+#[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
+pub enum ReasonCode {
+    Unspecified = 1,
+    PrevAuthNotValid = 2,
+    DeauthLeaving = 3,
+    DisassocDueToInactivity = 4,
+    DisassocApBusy = 5,
+    Class2FrameFromNonauthSta = 6,
+    Class3FrameFromNonassocSta = 7,
+    DisassocStaHasLeft = 8,
+    StaReqAssocWithoutAuth = 9,
+    PwrCapabilityNotValid = 10,
+    SupportedChannelNotValid = 11,
+    BssTransitionDisassoc = 12,
+    InvalidIe = 13,
+    MichaelMicFailure = 14,
+    FourwayHandshakeTimeout = 15,
+    GroupKeyUpdateTimeout = 16,
+    IeIn4wayDiffers = 17,
+    GroupCipherNotValid = 18,
+    PairwiseCipherNotValid = 19,
+    AkmpNotValid = 20,
+    UnsupportedRsnIeVersion = 21,
+    InvalidRsnIeCapab = 22,
+    Ieee8021xAuthFailed = 23,
+    CipherSuiteRejected = 24,
+    TdlsTeardownUnreachable = 25,
+    TdlsTeardownUnspecified = 26,
+    DisassocUnspecifiedQos = 32,
+    DisassocQapNoBandwidth = 33,
+    DisassocLowAck = 34,
+    DisassocQapExceedTxop = 35,
+    StaLeaving = 36,
+    StaTimeout = 39,
+}
+
+impl ReasonCode {
+    /// Human readable description of the reason, `None` if it isn't known by this crate
+    pub fn description(&self) -> Option<&'static str> {
+        Some(match self {
+            ReasonCode::Unspecified => "unspecified",
+            ReasonCode::PrevAuthNotValid => "previous authentication no longer valid",
+            ReasonCode::DeauthLeaving => "station is leaving",
+            ReasonCode::DisassocDueToInactivity => "inactivity",
+            ReasonCode::DisassocApBusy => "access point is unable to handle all stations",
+            ReasonCode::Class2FrameFromNonauthSta => {
+                "class 2 frame received from unauthenticated station"
+            }
+            ReasonCode::Class3FrameFromNonassocSta => {
+                "class 3 frame received from unassociated station"
+            }
+            ReasonCode::DisassocStaHasLeft => "station has left the BSS",
+            ReasonCode::StaReqAssocWithoutAuth => "association requested without authentication",
+            ReasonCode::PwrCapabilityNotValid => "power capability unacceptable",
+            ReasonCode::SupportedChannelNotValid => "supported channels unacceptable",
+            ReasonCode::BssTransitionDisassoc => "BSS transition management",
+            ReasonCode::InvalidIe => "invalid element",
+            ReasonCode::MichaelMicFailure => "MIC failure",
+            ReasonCode::FourwayHandshakeTimeout => "4-way handshake timeout",
+            ReasonCode::GroupKeyUpdateTimeout => "group key handshake timeout",
+            ReasonCode::IeIn4wayDiffers => {
+                "element in 4-way handshake differs from the (re)association frame"
+            }
+            ReasonCode::GroupCipherNotValid => "invalid group cipher",
+            ReasonCode::PairwiseCipherNotValid => "invalid pairwise cipher",
+            ReasonCode::AkmpNotValid => "invalid AKMP",
+            ReasonCode::UnsupportedRsnIeVersion => "unsupported RSNE version",
+            ReasonCode::InvalidRsnIeCapab => "invalid RSNE capabilities",
+            ReasonCode::Ieee8021xAuthFailed => "IEEE 802.1X authentication failed",
+            ReasonCode::CipherSuiteRejected => "cipher suite rejected by security policy",
+            ReasonCode::TdlsTeardownUnreachable => "TDLS peer unreachable",
+            ReasonCode::TdlsTeardownUnspecified => "TDLS teardown, unspecified",
+            ReasonCode::DisassocUnspecifiedQos => "unspecified QoS reason",
+            ReasonCode::DisassocQapNoBandwidth => "insufficient bandwidth for the station",
+            ReasonCode::DisassocLowAck => "excessive lost acknowledgements",
+            ReasonCode::DisassocQapExceedTxop => "station is transmitting outside its TXOPs",
+            ReasonCode::StaLeaving => "peer is leaving",
+            ReasonCode::StaTimeout => "peer timeout",
+            ReasonCode::UnrecognizedConst(_) => return None,
+        })
+    }
+}
+
+/// Formats the description, e.g. "4-way handshake timeout", or "reason 42" for unknown codes
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.description() {
+            Some(description) => f.write_str(description),
+            None => write!(f, "reason {}", u16::from(*self)),
+        }
+    }
+}
+
+/// IEEE 802.11 status code, sent in authentication and association responses and reported by
+/// [`Event::Connect`](crate::Event::Connect)
+///
+/// Table 9-50 of IEEE 802.11-2020
+#[neli_enum(serialized_type = "u16")]
+#[non_exhaustive]
+pub enum StatusCode {
+    Success = 0,
+    UnspecifiedFailure = 1,
+    CapsUnsupported = 10,
+    ReassocNoAssoc = 11,
+    AssocDeniedUnspec = 12,
+    NotSupportedAuthAlg = 13,
+    UnknownAuthTransaction = 14,
+    ChallengeFail = 15,
+    AuthTimeout = 16,
+    ApUnableToHandleNewSta = 17,
+    AssocDeniedRates = 18,
+    AssocRejectedTemporarily = 30,
+    RobustMgmtFramePolicyViolation = 31,
+    RequestDeclined = 37,
+    InvalidParameters = 38,
+    InvalidIe = 40,
+    GroupCipherNotValid = 41,
+    PairwiseCipherNotValid = 42,
+    AkmpNotValid = 43,
+    UnsupportedRsnIeVersion = 44,
+    InvalidRsnIeCapab = 45,
+    CipherRejectedPerPolicy = 46,
+    InvalidPmkid = 53,
+    InvalidMdie = 54,
+    InvalidFtie = 55,
+    AntiCloggingTokenRequired = 76,
+    FiniteCyclicGroupNotSupported = 77,
+    DeniedWithSuggestedBssTransition = 82,
+    UnknownPasswordIdentifier = 123,
+    SaeHashToElement = 126,
+    SaePk = 127,
+}
+
+impl StatusCode {
+    /// Human readable description of the status, `None` if it isn't known by this crate
+    pub fn description(&self) -> Option<&'static str> {
+        Some(match self {
+            StatusCode::Success => "success",
+            StatusCode::UnspecifiedFailure => "unspecified failure",
+            StatusCode::CapsUnsupported => "requested capabilities not supported",
+            StatusCode::ReassocNoAssoc => "reassociation denied, no existing association",
+            StatusCode::AssocDeniedUnspec => "association denied",
+            StatusCode::NotSupportedAuthAlg => "authentication algorithm not supported",
+            StatusCode::UnknownAuthTransaction => "unexpected authentication sequence number",
+            StatusCode::ChallengeFail => "challenge failure",
+            StatusCode::AuthTimeout => "authentication timeout",
+            StatusCode::ApUnableToHandleNewSta => "access point is unable to handle more stations",
+            StatusCode::AssocDeniedRates => "basic rates not supported",
+            StatusCode::AssocRejectedTemporarily => {
+                "association rejected temporarily, try again later"
+            }
+            StatusCode::RobustMgmtFramePolicyViolation => {
+                "robust management frame policy violation"
+            }
+            StatusCode::RequestDeclined => "request declined",
+            StatusCode::InvalidParameters => "invalid parameters",
+            StatusCode::InvalidIe => "invalid element",
+            StatusCode::GroupCipherNotValid => "invalid group cipher",
+            StatusCode::PairwiseCipherNotValid => "invalid pairwise cipher",
+            StatusCode::AkmpNotValid => "invalid AKMP",
+            StatusCode::UnsupportedRsnIeVersion => "unsupported RSNE version",
+            StatusCode::InvalidRsnIeCapab => "invalid RSNE capabilities",
+            StatusCode::CipherRejectedPerPolicy => "cipher suite rejected by security policy",
+            StatusCode::InvalidPmkid => "invalid PMKID",
+            StatusCode::InvalidMdie => "invalid MDE",
+            StatusCode::InvalidFtie => "invalid FTE",
+            StatusCode::AntiCloggingTokenRequired => "anti-clogging token required",
+            StatusCode::FiniteCyclicGroupNotSupported => "finite cyclic group not supported",
+            StatusCode::DeniedWithSuggestedBssTransition => "denied, a BSS transition is suggested",
+            StatusCode::UnknownPasswordIdentifier => "unknown password identifier",
+            StatusCode::SaeHashToElement => "SAE hash-to-element",
+            StatusCode::SaePk => "SAE-PK",
+            StatusCode::UnrecognizedConst(_) => return None,
+        })
+    }
+
+    /// Whether the status reports a success
+    pub fn is_success(&self) -> bool {
+        *self == StatusCode::Success
+    }
+}
+
+/// Formats the description, e.g. "authentication timeout", or "status 42" for unknown codes
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.description() {
+            Some(description) => f.write_str(description),
+            None => write!(f, "status {}", u16::from(*self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_reason {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ReasonCode::from(15u16).to_string(),
+            "4-way handshake timeout"
+        );
+        assert_eq!(ReasonCode::from(1000u16).to_string(), "reason 1000");
+        assert_eq!(
+            StatusCode::from(16u16).to_string(),
+            "authentication timeout"
+        );
+        assert_eq!(StatusCode::from(1000u16).to_string(), "status 1000");
+        assert!(StatusCode::from(0u16).is_success());
+    }
+}
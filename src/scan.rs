@@ -0,0 +1,261 @@
+use crate::attr::{
+    exact_payload, Attrs, NestedAttrs, Nl80211Attr, Nl80211SchedScanMatchAttr, PayloadExt,
+};
+use crate::event::Event;
+use crate::Socket;
+
+use neli::err::{DeError, NlError, SerError};
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// Parameters of a scan, see [`Socket::trigger_scan`](crate::Socket::trigger_scan)
+///
+/// The default parameters request a passive or wildcard scan of all the supported channels.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanParams {
+    /// SSIDs to probe for, an empty SSID probes for any network
+    pub ssids: Vec<Vec<u8>>,
+    /// Frequencies to scan (MHz), all the supported ones if empty
+    pub frequencies: Vec<u32>,
+    /// Extra information elements to add to the probe requests
+    pub ie: Option<Vec<u8>>,
+}
+
+impl ScanParams {
+    pub(crate) fn attrs(
+        &self,
+        interface_index: i32,
+    ) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        if !self.ssids.is_empty() {
+            let mut ssids = Nlattr::new(false, false, Nl80211Attr::AttrScanSsids, Buffer::new())?;
+            for (index, ssid) in (0u16..).zip(&self.ssids) {
+                ssids.add_nested_attribute(&Nlattr::new(false, false, index, ssid.as_slice())?)?;
+            }
+            attrs.push(ssids);
+        }
+        if !self.frequencies.is_empty() {
+            let mut frequencies = Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrScanFrequencies,
+                Buffer::new(),
+            )?;
+            for (index, frequency) in (0u16..).zip(&self.frequencies) {
+                frequencies.add_nested_attribute(&Nlattr::new(false, false, index, *frequency)?)?;
+            }
+            attrs.push(frequencies);
+        }
+        if let Some(ie) = &self.ie {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrIe,
+                ie.as_slice(),
+            )?);
+        }
+        Ok(attrs)
+    }
+}
+
+/// Parameters of a scheduled scan, see
+/// [`Socket::start_sched_scan`](crate::Socket::start_sched_scan)
+///
+/// The wiphy scans periodically in the background, possibly offloaded to the firmware, and
+/// reports new results with `NL80211_CMD_SCHED_SCAN_RESULTS` on the "scan" multicast group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedScanParams {
+    /// SSIDs, frequencies and extra information elements of each scan
+    pub scan: ScanParams,
+    /// Time between the start of two scans (ms), 10 s by default
+    pub interval: u32,
+    /// Only report the networks with one of these SSIDs, all the networks if empty
+    pub match_ssids: Vec<Vec<u8>>,
+    /// Stop the scheduled scan automatically when the socket that started it is closed
+    pub socket_owner: bool,
+}
+
+impl Default for SchedScanParams {
+    fn default() -> Self {
+        Self {
+            scan: ScanParams::default(),
+            interval: 10_000,
+            match_ssids: Vec::new(),
+            socket_owner: false,
+        }
+    }
+}
+
+impl SchedScanParams {
+    pub(crate) fn attrs(
+        &self,
+        interface_index: i32,
+    ) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+        let mut attrs = self.scan.attrs(interface_index)?;
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrSchedScanInterval,
+            self.interval,
+        )?);
+        if !self.match_ssids.is_empty() {
+            let mut sets =
+                Nlattr::new(false, false, Nl80211Attr::AttrSchedScanMatch, Buffer::new())?;
+            for (index, ssid) in (0u16..).zip(&self.match_ssids) {
+                let mut set = Nlattr::new(false, false, index, Buffer::new())?;
+                set.add_nested_attribute(&Nlattr::new(
+                    false,
+                    false,
+                    Nl80211SchedScanMatchAttr::SchedScanMatchAttrSsid,
+                    ssid.as_slice(),
+                )?)?;
+                sets.add_nested_attribute(&set)?;
+            }
+            attrs.push(sets);
+        }
+        if self.socket_owner {
+            attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrSocketOwner, ())?);
+        }
+        Ok(attrs)
+    }
+}
+
+/// Outcome of a scan, reported by [`Event::ScanDone`] once its results are available
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanInfo {
+    /// Index of the interface which scanned
+    pub index: Option<i32>,
+    /// Frequencies which were scanned (MHz)
+    pub frequencies: Vec<u32>,
+    /// TSF timer of the BSS in `start_tsf_bssid` when the scan started (µs)
+    pub start_tsf: Option<u64>,
+    /// BSSID of the BSS whose TSF timer is reported in `start_tsf`
+    pub start_tsf_bssid: Option<Vec<u8>>,
+    /// The scan was aborted, the results only cover part of the frequencies
+    pub aborted: bool,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for ScanInfo {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrIfindex => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrScanFrequencies => {
+                    for frequency in NestedAttrs::new(attr.nla_payload.as_ref()) {
+                        let (_, payload) = frequency?;
+                        res.frequencies
+                            .push(u32::from_ne_bytes(exact_payload(payload)?));
+                    }
+                }
+                Nl80211Attr::AttrScanStartTimeTsf => {
+                    res.start_tsf = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrScanStartTimeTsfBssid => {
+                    res.start_tsf_bssid = Some(attr.get_mac()?)
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Wait for the end of the scan of an interface on a socket subscribed to the "scan" group,
+/// discarding the other events
+pub(crate) fn wait_for_scan(
+    events: &mut Socket,
+    interface_index: i32,
+) -> Result<ScanInfo, NlError> {
+    loop {
+        match events.next_event()? {
+            Event::ScanDone(info) if info.index == Some(interface_index) => return Ok(info),
+            Event::EventsLost => return Err(NlError::msg("Scan events were lost")),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_scan {
+    use super::*;
+    use crate::cmd::Nl80211Cmd;
+    use neli::genl::Genlmsghdr;
+
+    #[test]
+    fn test_scan_done() {
+        let params = ScanParams {
+            frequencies: vec![2412, 5180],
+            ..Default::default()
+        };
+        let mut attrs = params.attrs(3).unwrap();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrScanStartTimeTsf, 42u64).unwrap());
+
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdNewScanResults, 1, attrs);
+        let expected = ScanInfo {
+            index: Some(3),
+            frequencies: vec![2412, 5180],
+            start_tsf: Some(42),
+            start_tsf_bssid: None,
+            aborted: false,
+        };
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::ScanDone(expected.clone())
+        );
+
+        let msg = Genlmsghdr::new(Nl80211Cmd::CmdScanAborted, 1, params.attrs(3).unwrap());
+        assert_eq!(
+            Event::try_from(&msg).unwrap(),
+            Event::ScanDone(ScanInfo {
+                start_tsf: None,
+                aborted: true,
+                ..expected
+            })
+        );
+    }
+
+    #[test]
+    fn test_sched_scan_attrs() {
+        let params = SchedScanParams {
+            interval: 30_000,
+            match_ssids: vec![b"home".to_vec()],
+            socket_owner: true,
+            ..Default::default()
+        };
+        let attrs = params.attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrSchedScanInterval)
+                .unwrap(),
+            30_000
+        );
+        assert!(attrs.get_attribute(Nl80211Attr::AttrSocketOwner).is_some());
+
+        let sets = attrs
+            .get_attribute(Nl80211Attr::AttrSchedScanMatch)
+            .unwrap();
+        let sets: Vec<_> = NestedAttrs::new(sets.nla_payload.as_ref())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(sets.len(), 1);
+        let set: Vec<_> = NestedAttrs::new(sets[0].1).map(Result::unwrap).collect();
+        assert_eq!(set, vec![(1, &b"home"[..])]);
+
+        let attrs = SchedScanParams::default().attrs(3).unwrap();
+        let attrs = attrs.get_attr_handle();
+        assert!(attrs
+            .get_attribute(Nl80211Attr::AttrSchedScanMatch)
+            .is_none());
+        assert!(attrs.get_attribute(Nl80211Attr::AttrSocketOwner).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Bss;
+use crate::Interface;
+use crate::Socket;
+use crate::Station;
+
+use neli::consts::genl::{CtrlAttr, CtrlCmd};
+use neli::consts::nl::GenlId;
+use neli::err::NlError;
+use neli::genl::Genlmsghdr;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A [`Socket`] which can be cloned and used from multiple threads
+///
+/// Requests are serialized by an internal lock, so the nl80211 family is resolved only once
+/// and responses of concurrent requests never get mixed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use neli_wifi::SharedSocket;
+/// # use std::error::Error;
+/// # use std::thread;
+///
+/// # fn main() -> Result<(), Box<dyn Error>>{
+///     let socket = SharedSocket::connect()?;
+///     let handles: Vec<_> = (0..4)
+///         .map(|_| {
+///             let socket = socket.clone();
+///             thread::spawn(move || socket.get_interfaces_info().map(|i| i.len()))
+///         })
+///         .collect();
+///     for handle in handles {
+///         println!("{:?}", handle.join().unwrap());
+///     }
+/// #   Ok(())
+/// # }
+///```
+#[derive(Clone)]
+pub struct SharedSocket {
+    inner: Arc<Mutex<Socket>>,
+}
+
+impl SharedSocket {
+    /// Create a new nl80211 socket with netlink
+    pub fn connect() -> Result<Self, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        Ok(Socket::connect()?.into())
+    }
+
+    /// Get exclusive access to the underlying socket, for the requests not wrapped by
+    /// [`SharedSocket`]
+    pub fn lock(&self) -> MutexGuard<'_, Socket> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get information for all your wifi interfaces, see [`Socket::get_interfaces_info`]
+    pub fn get_interfaces_info(&self) -> Result<Vec<Interface>, NlError> {
+        self.lock().get_interfaces_info()
+    }
+
+    /// Get access point information for a specific interface, see [`Socket::get_station_info`]
+    pub fn get_station_info(&self, interface_index: i32) -> Result<Station, NlError> {
+        self.lock().get_station_info(interface_index)
+    }
+
+    /// Get the scan results of a specific interface, see [`Socket::get_bss_info`]
+    pub fn get_bss_info(&self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
+        self.lock().get_bss_info(interface_index)
+    }
+}
+
+impl From<Socket> for SharedSocket {
+    fn from(sock: Socket) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(sock)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_shared_socket {
+    use super::*;
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedSocket>();
+    }
+}
@@ -0,0 +1,95 @@
+use crate::station::Station;
+
+/// Exponentially weighted moving average of the signal strength of a station, to base roaming
+/// decisions on a stable value rather than on noisy samples
+///
+/// Each sample moves the average by `alpha` times its difference with the average: a small
+/// `alpha` smooths more but reacts more slowly.
+///
+/// # Example
+///
+/// ```no_run
+/// use neli_wifi::{SignalSmoother, Socket};
+///
+/// let mut socket = Socket::connect()?;
+/// let mut smoother = SignalSmoother::new(0.2);
+/// let station = socket.get_station_info(3)?;
+/// if let Some(signal) = smoother.update_station(&station) {
+///     println!("{:.1} dBm", signal);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalSmoother {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl SignalSmoother {
+    /// Create a smoother with a weight between 0 (exclusive) and 1 (no smoothing), values out
+    /// of this range are clamped and NaN disables the smoothing
+    pub fn new(alpha: f64) -> Self {
+        let alpha = if alpha.is_nan() { 1.0 } else { alpha };
+        Self {
+            alpha: alpha.clamp(f64::EPSILON, 1.0),
+            value: None,
+        }
+    }
+
+    /// Add a sample (dBm) and return the new average, the first sample is taken as is
+    pub fn update(&mut self, signal: i8) -> f64 {
+        let signal = f64::from(signal);
+        let value = match self.value {
+            Some(value) => value + self.alpha * (signal - value),
+            None => signal,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// Add the signal of the last received PPDU of a station, or its average signal when the
+    /// driver doesn't report it, and return the new average
+    pub fn update_station(&mut self, station: &Station) -> Option<f64> {
+        let signal = station.signal.or(station.average_signal)?;
+        Some(self.update(signal))
+    }
+
+    /// Current average (dBm), `None` before the first sample
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Forget the previous samples, e.g. after roaming to another access point
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+#[cfg(test)]
+mod test_smoothing {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut smoother = SignalSmoother::new(0.25);
+        assert_eq!(smoother.value(), None);
+        assert_eq!(smoother.update(-60), -60.0);
+        assert_eq!(smoother.update(-80), -65.0);
+        assert_eq!(smoother.update(-65), -65.0);
+
+        let station = Station {
+            average_signal: Some(-45),
+            ..Default::default()
+        };
+        assert_eq!(smoother.update_station(&station), Some(-60.0));
+        assert_eq!(smoother.update_station(&Station::default()), None);
+
+        smoother.reset();
+        assert_eq!(smoother.update(-70), -70.0);
+        assert_eq!(SignalSmoother::new(2.0).update(-50), -50.0);
+
+        let mut smoother = SignalSmoother::new(f64::NAN);
+        smoother.update(-60);
+        assert_eq!(smoother.update(-70), -70.0);
+    }
+}
@@ -0,0 +1,316 @@
+use crate::socket::flatten_error;
+#[cfg(feature = "async")]
+use crate::AsyncSocket;
+use crate::{Bss, Interface, Socket, SocketBuilder, Station, Survey};
+
+use neli::err::NlError;
+
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::task::Poll;
+use std::thread;
+use std::time::SystemTime;
+
+/// State of all the wireless interfaces at one point in time, see
+/// [`WifiClient::snapshot`](crate::WifiClient::snapshot)
+///
+/// A failing query only fails its own field, so one misbehaving driver doesn't hide the other
+/// interfaces.
+#[derive(Debug)]
+pub struct WifiSnapshot {
+    /// When the interfaces were listed
+    pub taken_at: SystemTime,
+    /// The interfaces, in the order reported by the kernel
+    pub interfaces: Vec<InterfaceSnapshot>,
+    /// Channel survey of each wiphy having an interface, in the order of the interfaces
+    pub surveys: Vec<WiphySurvey>,
+}
+
+impl WifiSnapshot {
+    /// Channel survey of a wiphy, if it was taken successfully
+    pub fn survey(&self, phy: u32) -> Option<&[Survey]> {
+        self.surveys
+            .iter()
+            .find(|survey| survey.phy == phy)
+            .and_then(|survey| survey.survey.as_deref().ok())
+    }
+}
+
+/// State of one interface in a [`WifiSnapshot`]
+#[derive(Debug)]
+pub struct InterfaceSnapshot {
+    /// The interface itself
+    pub interface: Interface,
+    /// Access point of a station interface, see
+    /// [`Socket::get_station_info`](crate::Socket::get_station_info)
+    pub station: Result<Station, NlError>,
+    /// Scan results of the interface
+    pub bss: Result<Vec<Bss>, NlError>,
+}
+
+/// Channel survey of a wiphy in a [`WifiSnapshot`]
+#[derive(Debug)]
+pub struct WiphySurvey {
+    /// Index of the wiphy
+    pub phy: u32,
+    /// Survey dumped from one of the interfaces of the wiphy, see
+    /// [`Socket::get_survey`](crate::Socket::get_survey)
+    pub survey: Result<Vec<Survey>, NlError>,
+}
+
+/// A query of a snapshot, run concurrently with the others on its own socket
+enum Job {
+    /// Station and scan results of an interface
+    Interface(Interface),
+    /// Survey of a wiphy, from the interface with the given index
+    Survey(u32, i32),
+}
+
+enum Done {
+    Interface(Box<InterfaceSnapshot>),
+    Survey(WiphySurvey),
+}
+
+impl Job {
+    /// An interface job for each interface, and a survey job for each wiphy using its first
+    /// interface
+    fn plan(interfaces: Vec<Interface>) -> Vec<Self> {
+        let mut surveys = Vec::new();
+        for interface in &interfaces {
+            if let (Some(phy), Some(index)) = (interface.phy, interface.index) {
+                if !surveys
+                    .iter()
+                    .any(|job| matches!(job, Job::Survey(known, _) if *known == phy))
+                {
+                    surveys.push(Job::Survey(phy, index));
+                }
+            }
+        }
+        interfaces
+            .into_iter()
+            .map(Job::Interface)
+            .chain(surveys)
+            .collect()
+    }
+
+    fn run(self, options: &SocketBuilder) -> Done {
+        match self {
+            Job::Interface(interface) => {
+                let (station, bss) = match interface.index {
+                    Some(index) => match connect(options) {
+                        Ok(mut socket) => {
+                            (socket.get_station_info(index), socket.get_bss_info(index))
+                        }
+                        Err(err) => (Err(copy_error(&err)), Err(err)),
+                    },
+                    None => (Err(no_index()), Err(no_index())),
+                };
+                Done::Interface(Box::new(InterfaceSnapshot {
+                    interface,
+                    station,
+                    bss,
+                }))
+            }
+            Job::Survey(phy, index) => Done::Survey(WiphySurvey {
+                phy,
+                survey: connect(options).and_then(|mut socket| socket.get_survey(index)),
+            }),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn run_async(self, options: &SocketBuilder) -> Done {
+        match self {
+            Job::Interface(interface) => {
+                let (station, bss) = match interface.index {
+                    Some(index) => match connect_async(options) {
+                        Ok(mut socket) => (
+                            socket.get_station_info(index).await,
+                            socket.get_bss_info(index).await,
+                        ),
+                        Err(err) => (Err(copy_error(&err)), Err(err)),
+                    },
+                    None => (Err(no_index()), Err(no_index())),
+                };
+                Done::Interface(Box::new(InterfaceSnapshot {
+                    interface,
+                    station,
+                    bss,
+                }))
+            }
+            Job::Survey(phy, index) => {
+                let survey = match connect_async(options) {
+                    Ok(mut socket) => socket.get_survey(index).await,
+                    Err(err) => Err(err),
+                };
+                Done::Survey(WiphySurvey { phy, survey })
+            }
+        }
+    }
+}
+
+impl WifiSnapshot {
+    fn new(taken_at: SystemTime, done: Vec<Done>) -> Self {
+        let mut snapshot = Self {
+            taken_at,
+            interfaces: Vec::new(),
+            surveys: Vec::new(),
+        };
+        for done in done {
+            match done {
+                Done::Interface(interface) => snapshot.interfaces.push(*interface),
+                Done::Survey(survey) => snapshot.surveys.push(survey),
+            }
+        }
+        snapshot
+    }
+}
+
+/// List the interfaces on `socket`, then query each interface and wiphy from its own thread and
+/// socket
+pub(crate) fn take_snapshot(socket: &mut Socket) -> Result<WifiSnapshot, NlError> {
+    let taken_at = SystemTime::now();
+    let jobs = Job::plan(socket.get_interfaces_info()?);
+    let options = socket.options();
+
+    let done = thread::scope(|scope| {
+        let handles: Vec<_> = jobs
+            .into_iter()
+            .map(|job| scope.spawn(move || job.run(options)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect()
+    });
+    Ok(WifiSnapshot::new(taken_at, done))
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl AsyncSocket {
+    /// Get the interfaces with their station and scan results, and the survey of their
+    /// wiphys, see [`WifiClient::snapshot`](crate::WifiClient::snapshot)
+    ///
+    /// The interfaces are listed on this socket, then each interface and wiphy is queried
+    /// concurrently on its own socket, without spawning tasks.
+    pub async fn snapshot(&mut self) -> Result<WifiSnapshot, NlError> {
+        let taken_at = SystemTime::now();
+        let jobs = Job::plan(self.get_interfaces_info().await?);
+        let options = self.options();
+        let done = join_all(jobs.into_iter().map(|job| job.run_async(options))).await;
+        Ok(WifiSnapshot::new(taken_at, done))
+    }
+}
+
+/// Poll futures concurrently on the current task and collect their outputs in order
+#[cfg(feature = "async")]
+async fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> Vec<F::Output> {
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    std::future::poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(&mut outputs) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => *output = Some(value),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+fn connect(options: &SocketBuilder) -> Result<Socket, NlError> {
+    options.clone().connect().map_err(flatten_error)
+}
+
+#[cfg(feature = "async")]
+fn connect_async(options: &SocketBuilder) -> Result<AsyncSocket, NlError> {
+    Ok(connect(options)?.try_into()?)
+}
+
+fn no_index() -> NlError {
+    NlError::msg("The interface has no index")
+}
+
+/// [`NlError`] can't be cloned, report the same failure for both queries of an interface
+fn copy_error(err: &NlError) -> NlError {
+    NlError::msg(err.to_string())
+}
+
+#[cfg(test)]
+mod test_snapshot {
+    use super::*;
+
+    fn interface(index: Option<i32>, phy: u32) -> Interface {
+        Interface {
+            index,
+            phy: Some(phy),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let jobs = Job::plan(vec![
+            interface(Some(3), 0),
+            interface(Some(4), 0),
+            interface(None, 1),
+            interface(Some(6), 1),
+        ]);
+        let surveys: Vec<_> = jobs
+            .iter()
+            .filter_map(|job| match job {
+                Job::Survey(phy, index) => Some((*phy, *index)),
+                Job::Interface(_) => None,
+            })
+            .collect();
+        assert_eq!(jobs.len(), 6);
+        assert_eq!(surveys, vec![(0, 3), (1, 6)]);
+    }
+
+    #[test]
+    fn test_partial_failure() {
+        let done = vec![
+            Job::Interface(interface(None, 1)).run(&SocketBuilder::new()),
+            Done::Survey(WiphySurvey {
+                phy: 0,
+                survey: Ok(vec![Survey::default()]),
+            }),
+            Done::Survey(WiphySurvey {
+                phy: 1,
+                survey: Err(NlError::msg("The wiphy went away")),
+            }),
+        ];
+        let snapshot = WifiSnapshot::new(SystemTime::now(), done);
+        assert_eq!(snapshot.interfaces.len(), 1);
+        assert!(snapshot.interfaces[0].station.is_err());
+        assert!(snapshot.interfaces[0].bss.is_err());
+        assert_eq!(snapshot.survey(0).map(<[Survey]>::len), Some(1));
+        assert!(snapshot.survey(1).is_none());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_join_all() {
+        let outputs = join_all((0..3).map(|i| async move {
+            tokio::task::yield_now().await;
+            i * 2
+        }))
+        .await;
+        assert_eq!(outputs, vec![0, 2, 4]);
+    }
+}
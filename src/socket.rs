@@ -1,30 +1,273 @@
-use crate::attr::Nl80211Attr;
-use crate::bss::Bss;
+use crate::ap::{ApConfig, BssConfig};
+use crate::attr::{Attrs, Nl80211Attr, Nl80211Iftype, Nl80211MntrFlags};
+use crate::bss::{Bss, BssRef};
 use crate::cmd::Nl80211Cmd;
+use crate::connect::ConnectParams;
+use crate::event::Event;
 use crate::interface::Interface;
+use crate::monitor::{cookie_attrs, frame_attrs, new_interface_attrs, remain_on_channel_attrs};
+use crate::parse::{parse_attrs, ParseWarning};
+use crate::power::{tx_power_attrs, Mbm, TxPower};
+use crate::reason::ReasonCode;
+use crate::scan::{wait_for_scan, ScanInfo, ScanParams, SchedScanParams};
 use crate::station::Station;
+use crate::survey::{merge_surveys, Survey};
+use crate::txq::{txq_limits_attrs, txq_params_attrs, TxqLimits, TxqParams};
+use crate::wiphy::{coverage_class_for_distance, merge_wiphys, Wiphy};
 use crate::{NL_80211_GENL_NAME, NL_80211_GENL_VERSION};
 
 use neli::consts::genl::{CtrlAttr, CtrlCmd};
 use neli::consts::{nl::GenlId, nl::NlmF, nl::NlmFFlags, nl::Nlmsg, socket::NlFamily};
-use neli::err::NlError;
+use neli::err::{DeError, NlError, SerError, WrappedError};
 use neli::genl::{Genlmsghdr, Nlattr};
 use neli::nl::{NlPayload, Nlmsghdr};
 use neli::socket::NlSocketHandle;
-use neli::types::GenlBuffer;
+use neli::types::{Buffer, GenlBuffer};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+
+type Nl80211Msg = Genlmsghdr<Nl80211Cmd, Nl80211Attr>;
+
+/// Options for creating a [`Socket`]
+///
+/// # Example
+///
+/// ```no_run
+/// # use neli_wifi::SocketBuilder;
+/// # use std::error::Error;
+///
+/// # fn main() -> Result<(), Box<dyn Error>>{
+///     let mut socket = SocketBuilder::new().auto_reconnect(true).connect()?;
+///     socket.subscribe("mlme")?;
+///     loop {
+///         println!("{:#?}", socket.next_event()?);
+///     }
+/// # }
+///```
+#[derive(Debug, Default, Clone)]
+pub struct SocketBuilder {
+    auto_reconnect: bool,
+    recv_buffer_size: Option<usize>,
+    genl_version: Option<u8>,
+    lenient: bool,
+}
+
+impl SocketBuilder {
+    /// Create a builder with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-open the socket and resolve the nl80211 family again when sending or receiving fails
+    /// (e.g. with `ENOBUFS` after a netlink buffer overrun), instead of returning the error.
+    ///
+    /// Failed queries (dumps and `GET` commands) are retried once on the new socket. Other
+    /// commands fail with the socket error instead, as the kernel may have run them already.
+    /// Multicast groups joined with
+    /// [`Socket::subscribe`] are joined again, but frame registrations are lost. An overrun
+    /// while waiting for events is reported as [`Event::EventsLost`] rather than reconnecting.
+    pub fn auto_reconnect(mut self, auto_reconnect: bool) -> Self {
+        self.auto_reconnect = auto_reconnect;
+        self
+    }
+
+    /// Size of the kernel receive buffer of the socket (bytes)
+    ///
+    /// A bigger buffer makes it less likely for events to be dropped (see
+    /// [`Event::EventsLost`]) when they arrive faster than they are read. Sizes above
+    /// `net.core.rmem_max` need the `CAP_NET_ADMIN` capability.
+    pub fn recv_buffer_size(mut self, size: usize) -> Self {
+        self.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Version of the nl80211 protocol sent in the generic netlink header of the requests,
+    /// [`NL_80211_GENL_VERSION`] by default
+    pub fn genl_version(mut self, version: u8) -> Self {
+        self.genl_version = Some(version);
+        self
+    }
+
+    /// Skip the attributes which fail to be parsed instead of failing the whole request, e.g.
+    /// when a kernel changes the format of an attribute
+    ///
+    /// Applies to the information returned by the `get_*` methods. The skipped attributes can
+    /// be retrieved with [`Socket::take_warnings`]. Only top-level attributes are skipped: a
+    /// malformed attribute nested in another one, e.g. in the station information, skips the
+    /// whole enclosing attribute.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Create a new nl80211 socket with netlink
+    pub fn connect(self) -> Result<Socket, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        let (sock, family_id) = self.open()?;
+        Ok(Socket {
+            sock,
+            family_id,
+            options: self,
+            groups: Vec::new(),
+            seq: 0,
+            warnings: Vec::new(),
+            notifications: VecDeque::new(),
+            recv_timeout: None,
+            #[cfg(feature = "rtnetlink")]
+            link: None,
+        })
+    }
+
+    pub(crate) fn version(&self) -> u8 {
+        self.genl_version.unwrap_or(NL_80211_GENL_VERSION)
+    }
+
+    pub(crate) fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    fn open(
+        &self,
+    ) -> Result<(NlSocketHandle, u16), NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        let mut sock = NlSocketHandle::connect(NlFamily::Generic, None, &[])?;
+        if let Some(size) = self.recv_buffer_size {
+            set_recv_buffer_size(&sock, size)?;
+        }
+        let family_id = sock
+            .resolve_genl_family(NL_80211_GENL_NAME)
+            .map_err(not_available)?;
+        Ok((sock, family_id))
+    }
+}
+
+/// Reason of the error returned when connecting while the kernel has no nl80211 family, e.g.
+/// without any Wi-Fi hardware or when cfg80211 isn't loaded
+///
+/// The error is wrapped in an [`io::Error`] of kind [`io::ErrorKind::NotFound`], use
+/// [`Nl80211NotAvailable::matches`] to recognize it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nl80211NotAvailable;
+
+impl Nl80211NotAvailable {
+    /// Whether the error was returned because nl80211 is not available
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211NotAvailable, Socket};
+    /// match Socket::connect() {
+    ///     Ok(socket) => println!("Wi-Fi is available"),
+    ///     Err(err) if Nl80211NotAvailable::matches(&err) => println!("No Wi-Fi on this system"),
+    ///     Err(err) => eprintln!("Netlink error: {}", err),
+    /// }
+    /// ```
+    pub fn matches<T, P>(err: &NlError<T, P>) -> bool {
+        match err {
+            NlError::Wrapped(WrappedError::IOError(err)) => err
+                .get_ref()
+                .is_some_and(|err| err.is::<Nl80211NotAvailable>()),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Nl80211NotAvailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("nl80211 is not available")
+    }
+}
+
+impl std::error::Error for Nl80211NotAvailable {}
 
 /// A generic netlink socket to send commands and receive messages
 pub struct Socket {
     pub(crate) sock: NlSocketHandle,
     pub(crate) family_id: u16,
+    options: SocketBuilder,
+    groups: Vec<String>,
+    pub(crate) seq: u32,
+    warnings: Vec<ParseWarning>,
+    pub(crate) notifications: VecDeque<Nl80211Msg>,
+    recv_timeout: Option<Duration>,
+    #[cfg(feature = "rtnetlink")]
+    pub(crate) link: Option<crate::link::LinkSocket>,
 }
 
 impl Socket {
     /// Create a new nl80211 socket with netlink
     pub fn connect() -> Result<Self, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
-        let mut sock = NlSocketHandle::connect(NlFamily::Generic, None, &[])?;
-        let family_id = sock.resolve_genl_family(NL_80211_GENL_NAME)?;
-        Ok(Self { sock, family_id })
+        SocketBuilder::new().connect()
+    }
+
+    /// Whether the kernel provides nl80211, i.e. whether a socket can be connected
+    ///
+    /// Lets applications hide their Wi-Fi features on systems without Wi-Fi hardware instead of
+    /// handling the error of [`Socket::connect`]. Errors other than [`Nl80211NotAvailable`],
+    /// e.g. when netlink sockets can't be created, are returned.
+    pub fn is_available() -> Result<bool, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        match Self::connect() {
+            Ok(_) => Ok(true),
+            Err(err) if Nl80211NotAvailable::matches(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create a socket with non-default options
+    pub fn builder() -> SocketBuilder {
+        SocketBuilder::new()
+    }
+
+    /// Options the socket was created with, to open more sockets alike
+    pub(crate) fn options(&self) -> &SocketBuilder {
+        &self.options
+    }
+
+    /// Take the warnings about attributes skipped since the last call, see
+    /// [`SocketBuilder::lenient`]
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Replace the underlying netlink socket with a new one
+    ///
+    /// The nl80211 family is resolved again and the multicast groups joined with
+    /// [`Socket::subscribe`] are joined again. Frame and beacon registrations are lost.
+    pub fn reconnect(&mut self) -> Result<(), NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        let (mut sock, family_id) = self.options.open()?;
+        for group in &self.groups {
+            let id = sock.resolve_nl_mcast_group(NL_80211_GENL_NAME, group)?;
+            sock.add_mcast_membership(&[id])?;
+        }
+        self.sock = sock;
+        self.family_id = family_id;
+        Ok(())
+    }
+
+    /// Make requests fail with [`io::ErrorKind::TimedOut`] when no reply was received for
+    /// `timeout`, or wait forever with `None`
+    pub fn set_recv_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        let duration = timeout.unwrap_or_default();
+        let timeval = libc::timeval {
+            tv_sec: duration.as_secs().try_into().unwrap_or(libc::time_t::MAX),
+            tv_usec: duration.subsec_micros().into(),
+        };
+        let res = unsafe {
+            libc::setsockopt(
+                self.sock.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeval as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        self.recv_timeout = timeout;
+        Ok(())
     }
 
     /// Get information for all your wifi interfaces
@@ -44,40 +287,13 @@ impl Socket {
     /// # }
     ///```
     pub fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetInterface,
-            NL_80211_GENL_VERSION,
-            GenlBuffer::new(),
-        );
-
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
-
-        self.sock.send(nlhdr)?;
-
-        let iter = self
-            .sock
-            .iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(false);
+        let replies = self.dump(Nl80211Cmd::CmdGetInterface, GenlBuffer::new())?;
         let mut interfaces = Vec::new();
-        for response in iter {
-            let response = response.unwrap();
-            match response.nl_type {
-                Nlmsg::Noop => (),
-                Nlmsg::Error => panic!("Error"),
-                Nlmsg::Done => break,
-                _ => {
-                    let handle = response.nl_payload.get_payload().unwrap().get_attr_handle();
-                    interfaces.push(handle.try_into()?);
-                }
-            };
+        for reply in replies {
+            interfaces.push(self.parse(&reply)?);
         }
+        #[cfg(feature = "rtnetlink")]
+        self.fill_link_states(&mut interfaces)?;
 
         Ok(interfaces)
     }
@@ -105,74 +321,622 @@ impl Socket {
     /// # }
     ///```
     pub fn get_station_info(&mut self, interface_index: i32) -> Result<Station, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetStation,
-            NL_80211_GENL_VERSION,
-            {
-                let mut attrs = GenlBuffer::new();
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
-                attrs
-            },
-        );
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetStation, attrs)?;
+        match replies.last() {
+            Some(reply) => Ok(self.parse(reply)?),
+            None => Ok(Station::default()),
+        }
+    }
 
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+    pub fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetScan, attrs)?;
+        let mut retval = Vec::new();
+        for reply in replies {
+            retval.push(self.parse(&reply)?);
+        }
 
-        self.sock.send(nlhdr)?;
+        Ok(retval)
+    }
+
+    /// Call `f` with each scan result of a specific interface, borrowing the BSSID and
+    /// information elements from the reply instead of copying them as
+    /// [`get_bss_info`](Self::get_bss_info) does
+    pub fn for_each_bss<F>(&mut self, interface_index: i32, mut f: F) -> Result<(), NlError>
+    where
+        F: FnMut(BssRef<'_>),
+    {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetScan, attrs)?;
+        for reply in replies {
+            let attrs = reply.get_attr_handle();
+            f(BssRef::try_from(&attrs)?);
+        }
+
+        Ok(())
+    }
+
+    /// Start a scan on an interface, its end is reported as [`Event::ScanDone`] on the "scan"
+    /// multicast group
+    pub fn trigger_scan(
+        &mut self,
+        interface_index: i32,
+        params: &ScanParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdTriggerScan, attrs)
+    }
+
+    /// Scan on an interface and wait for the scan to end, the results can then be fetched with
+    /// [`get_bss_info`](Self::get_bss_info)
+    ///
+    /// The end of the scan is received on a temporary socket joining the "scan" multicast
+    /// group, so the events of this socket aren't consumed. The receive timeout set with
+    /// [`set_recv_timeout`](Self::set_recv_timeout) applies to the wait.
+    pub fn scan(&mut self, interface_index: i32, params: &ScanParams) -> Result<ScanInfo, NlError> {
+        let mut events = self.options.clone().connect().map_err(flatten_error)?;
+        events.set_recv_timeout(self.recv_timeout)?;
+        events.subscribe("scan").map_err(flatten_error)?;
+        self.trigger_scan(interface_index, params)?;
+        wait_for_scan(&mut events, interface_index)
+    }
+
+    /// Start scanning periodically in the background on an interface, until
+    /// [`stop_sched_scan`](Self::stop_sched_scan) is called
+    ///
+    /// With [`SchedScanParams::socket_owner`], the kernel stops the scheduled scan once this
+    /// socket is closed, e.g. when the process exits without stopping it.
+    pub fn start_sched_scan(
+        &mut self,
+        interface_index: i32,
+        params: &SchedScanParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdStartSchedScan, attrs)
+    }
+
+    /// Stop the scheduled scan of an interface
+    pub fn stop_sched_scan(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdStopSchedScan, attrs)
+    }
+
+    /// Get information for all your wiphys
+    pub fn get_wiphys(&mut self) -> Result<Vec<Wiphy>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrSplitWiphyDump,
+            (),
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetWiphy, attrs)?;
+        let mut parts = Vec::new();
+        for reply in replies {
+            parts.push(self.parse(&reply)?);
+        }
+
+        Ok(merge_wiphys(parts))
+    }
+
+    /// Get the wiphy with the given name, e.g. "phy0"
+    pub fn get_wiphy_by_name(&mut self, name: &str) -> Result<Option<Wiphy>, NlError> {
+        let wiphys = self.get_wiphys()?;
+        Ok(wiphys
+            .into_iter()
+            .find(|wiphy| wiphy.name.as_deref() == Some(name)))
+    }
+
+    /// Get the channel survey of the wiphy of a specific interface
+    pub fn get_survey(&mut self, interface_index: i32) -> Result<Vec<Survey>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.dump(Nl80211Cmd::CmdGetSurvey, attrs)?;
+        let mut surveys = Vec::new();
+        for reply in replies {
+            surveys.push(self.parse(&reply)?);
+        }
+
+        Ok(surveys)
+    }
+
+    /// Get the channel survey of a wiphy, with one entry per frequency
+    ///
+    /// The survey is requested through any interface of the wiphy, and entries reported
+    /// more than once for the same frequency are merged.
+    pub fn get_survey_for_phy(&mut self, phy: u32) -> Result<Vec<Survey>, NlError> {
+        let index = self
+            .get_interfaces_info()?
+            .into_iter()
+            .find(|interface| interface.phy == Some(phy))
+            .and_then(|interface| interface.index)
+            .ok_or_else(|| NlError::msg("The wiphy has no network interface"))?;
+        Ok(merge_surveys(self.get_survey(index)?))
+    }
+
+    /// Tune the interface to a frequency (MHz), optionally with a KHz offset for S1G channels.
+    ///
+    /// This is mostly useful for monitor interfaces, other interface types usually select
+    /// their channel on their own.
+    pub fn set_frequency(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        frequency_offset: Option<u32>,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyFreq,
+            frequency,
+        )?);
+        if let Some(offset) = frequency_offset {
+            attrs.push(Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrWiphyFreqOffset,
+                offset,
+            )?);
+        }
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Connect the interface to a network
+    ///
+    /// This only asks the driver to start connecting, the outcome is reported as a
+    /// [`Event::Connect`] on the "mlme" multicast group.
+    pub fn connect_network(
+        &mut self,
+        interface_index: i32,
+        params: &ConnectParams,
+    ) -> Result<(), NlError> {
+        let attrs = params.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdConnect, attrs)
+    }
+
+    /// Disconnect the interface from the current network
+    pub fn disconnect(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrReasonCode,
+            u16::from(ReasonCode::DeauthLeaving),
+        )?);
+        self.execute(Nl80211Cmd::CmdDisconnect, attrs)
+    }
+
+    /// Create a new virtual interface on a wiphy
+    ///
+    /// If `socket_owner` is set, the kernel removes the interface once the socket is closed,
+    /// including when the process crashes.
+    pub fn new_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        let attrs = new_interface_attrs(phy, name, iftype, socket_owner, &[])?;
+        self.create_interface(attrs)
+    }
+
+    /// Create a monitor interface on a wiphy, with monitor flags such as
+    /// [`MntrFlagActive`](Nl80211MntrFlags::MntrFlagActive) to acknowledge frames sent to the
+    /// interface's address, see [`InjectionSocket`](crate::InjectionSocket) for frame injection
+    pub fn new_monitor_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        flags: &[Nl80211MntrFlags],
+        socket_owner: bool,
+    ) -> Result<Interface, NlError> {
+        let iftype = Nl80211Iftype::IftypeMonitor;
+        let attrs = new_interface_attrs(phy, name, iftype, socket_owner, flags)?;
+        self.create_interface(attrs)
+    }
+
+    fn create_interface(
+        &mut self,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Interface, NlError> {
+        let replies = self.request(Nl80211Cmd::CmdNewInterface, attrs)?;
+        match replies.first() {
+            Some(reply) => Ok(self.parse(reply)?),
+            None => Err(NlError::msg("No interface was returned by the kernel")),
+        }
+    }
+
+    /// Transmit a management frame, starting with its 802.11 header, on a non-monitor interface
+    ///
+    /// The frame is sent on `frequency` if set, going off-channel if needed, and on the
+    /// operating channel otherwise. With `wait_for_ack`, the kernel returns a cookie matching
+    /// the `NL80211_CMD_FRAME_TX_STATUS` notification of the frame.
+    pub fn send_frame(
+        &mut self,
+        interface_index: i32,
+        frequency: Option<u32>,
+        frame: &[u8],
+        wait_for_ack: bool,
+    ) -> Result<Option<u64>, NlError> {
+        let attrs = frame_attrs(interface_index, frequency, frame, wait_for_ack)?;
+        let replies = self.request(Nl80211Cmd::CmdFrame, attrs)?;
+        let cookie = replies.first().and_then(|reply| {
+            reply
+                .get_attr_handle()
+                .get_attr_payload_as::<u64>(Nl80211Attr::AttrCookie)
+                .ok()
+        });
+        Ok(cookie)
+    }
+
+    /// Stay on the channel of `frequency` for `duration` ms on an interface, e.g. to exchange
+    /// frames with [`send_frame`](Self::send_frame) off the operating channel
+    ///
+    /// Returns the cookie of the request, to end it early with
+    /// [`cancel_remain_on_channel`](Self::cancel_remain_on_channel). Unlike interfaces, APs and
+    /// scheduled scans, the kernel doesn't tie it to the requesting socket, so there is no
+    /// `socket_owner` option, but it always ends after `duration`, which the driver bounds.
+    pub fn remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        frequency: u32,
+        duration: u32,
+    ) -> Result<u64, NlError> {
+        let attrs = remain_on_channel_attrs(interface_index, frequency, duration)?;
+        let replies = self.request(Nl80211Cmd::CmdRemainOnChannel, attrs)?;
+        replies
+            .first()
+            .and_then(|reply| {
+                reply
+                    .get_attr_handle()
+                    .get_attr_payload_as::<u64>(Nl80211Attr::AttrCookie)
+                    .ok()
+            })
+            .ok_or_else(|| NlError::msg("No cookie was returned by the kernel"))
+    }
+
+    /// End a remain-on-channel before its duration, using the cookie returned when starting it
+    pub fn cancel_remain_on_channel(
+        &mut self,
+        interface_index: i32,
+        cookie: u64,
+    ) -> Result<(), NlError> {
+        let attrs = cookie_attrs(interface_index, cookie)?;
+        self.execute(Nl80211Cmd::CmdCancelRemainOnChannel, attrs)
+    }
+
+    /// Remove a virtual interface
+    pub fn del_interface(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdDelInterface, attrs)
+    }
+
+    /// Start operating as an access point on an AP interface
+    pub fn start_ap(&mut self, interface_index: i32, config: &ApConfig) -> Result<(), NlError> {
+        let attrs = config.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdStartAp, attrs)
+    }
+
+    /// Stop operating as an access point
+    pub fn stop_ap(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        self.execute(Nl80211Cmd::CmdStopAp, attrs)
+    }
 
-        let iter = self
+    /// Change the BSS parameters of an AP or P2P GO interface, such as client isolation
+    pub fn set_bss(&mut self, interface_index: i32, config: &BssConfig) -> Result<(), NlError> {
+        let attrs = config.attrs(interface_index)?;
+        self.execute(Nl80211Cmd::CmdSetBss, attrs)
+    }
+
+    /// Get the transmit power level of an interface
+    pub fn get_tx_power(&mut self, interface_index: i32) -> Result<Option<Mbm>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        let replies = self.request(Nl80211Cmd::CmdGetInterface, attrs)?;
+        match replies.first() {
+            Some(reply) => Ok(self.parse::<Interface>(reply)?.tx_power()),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the transmit power of an interface, or of its whole wiphy for drivers which don't
+    /// support per interface settings
+    pub fn set_tx_power(&mut self, interface_index: i32, power: TxPower) -> Result<(), NlError> {
+        let attrs = tx_power_attrs(interface_index, power)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Set the coverage class of a wiphy, to extend its slot time and ACK timeout for long
+    /// distance links
+    pub fn set_coverage_class(&mut self, phy: u32, coverage_class: u8) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWiphyCoverageClass,
+            coverage_class,
+        )?);
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Set the coverage class of a wiphy for a link of `distance` meters, see
+    /// [`coverage_class_for_distance`]
+    pub fn set_distance(&mut self, phy: u32, distance: u32) -> Result<(), NlError> {
+        self.set_coverage_class(phy, coverage_class_for_distance(distance))
+    }
+
+    /// Set the EDCA parameters of transmit queues of an AP or P2P GO interface
+    pub fn set_txq_params(
+        &mut self,
+        interface_index: i32,
+        params: &[TxqParams],
+    ) -> Result<(), NlError> {
+        let attrs = txq_params_attrs(interface_index, params)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Change the limits of the transmit queues of a wiphy, the current ones are reported by
+    /// [`get_wiphys`](Self::get_wiphys)
+    pub fn set_txq_limits(&mut self, phy: u32, limits: &TxqLimits) -> Result<(), NlError> {
+        let attrs = txq_limits_attrs(phy, limits)?;
+        self.execute(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Join an nl80211 multicast group, such as "config", "scan", "regulatory" or "mlme", to
+    /// receive its notifications with [`Socket::next_event`]
+    pub fn subscribe(
+        &mut self,
+        group: &str,
+    ) -> Result<(), NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
+        let id = self
             .sock
-            .iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(false);
-        let mut retval = None;
-        for response in iter {
-            let response = response.unwrap();
-            match response.nl_type {
-                Nlmsg::Noop => (),
-                Nlmsg::Error => panic!("Error"),
-                Nlmsg::Done => break,
-                _ => {
-                    retval = Some(
-                        response
-                            .nl_payload
-                            .get_payload()
-                            .unwrap()
-                            .get_attr_handle()
-                            .try_into()?,
-                    );
+            .resolve_nl_mcast_group(NL_80211_GENL_NAME, group)?;
+        self.sock.add_mcast_membership(&[id])?;
+        if !self.groups.iter().any(|g| g == group) {
+            self.groups.push(group.to_string());
+        }
+        Ok(())
+    }
+
+    /// Receive management frames of the given type whose body starts with `frame_match`
+    /// (e.g. [`FRAME_TYPE_PROBE_REQ`](crate::FRAME_TYPE_PROBE_REQ) with an empty match) on
+    /// this socket, as [`Event::Frame`] events.
+    ///
+    /// The registration lasts as long as the socket is open.
+    pub fn register_frame(
+        &mut self,
+        interface_index: i32,
+        frame_type: u16,
+        frame_match: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrIfindex,
+            interface_index,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrFrameType,
+            frame_type,
+        )?);
+        attrs.push(Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrFrameMatch,
+            frame_match,
+        )?);
+        self.execute(Nl80211Cmd::CmdRegisterFrame, attrs)
+    }
+
+    /// Receive the action frames of a category (e.g. [`ACTION_CATEGORY_WNM`](crate::ACTION_CATEGORY_WNM)
+    /// for BSS transition management) on this socket, see [`Socket::register_frame`]
+    pub fn register_action_frames(
+        &mut self,
+        interface_index: i32,
+        category: u8,
+    ) -> Result<(), NlError> {
+        self.register_frame(interface_index, crate::FRAME_TYPE_ACTION, &[category])
+    }
+
+    /// Receive the beacons of other BSSes heard by the wiphy on this socket, as [`Event::Frame`]
+    /// events.
+    ///
+    /// The registration lasts as long as the socket is open.
+    pub fn register_beacons(&mut self, phy: u32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+        self.execute(Nl80211Cmd::CmdRegisterBeacons, attrs)
+    }
+
+    /// Wait for the next event sent to this socket
+    ///
+    /// Notifications received while waiting for the reply to a command are returned first. Fails
+    /// with [`io::ErrorKind::TimedOut`] if no event arrives before the receive timeout set with
+    /// [`set_recv_timeout`](Self::set_recv_timeout). If the kernel had to drop events because the receive buffer was full,
+    /// [`Event::EventsLost`] is returned once and the following events are received normally.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::Socket;
+    /// # use std::error::Error;
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    ///     let mut socket = Socket::connect()?;
+    ///     socket.subscribe("mlme")?;
+    ///     loop {
+    ///         println!("{:#?}", socket.next_event()?);
+    ///     }
+    /// # }
+    ///```
+    pub fn next_event(&mut self) -> Result<Event, NlError> {
+        if let Some(msg) = self.notifications.pop_front() {
+            return Ok((&msg).try_into()?);
+        }
+        loop {
+            let response = match self
+                .sock
+                .recv::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>()
+            {
+                Err(NlError::Wrapped(err)) if is_events_lost(&err) => return Ok(Event::EventsLost),
+                Err(err) if self.options.auto_reconnect && is_socket_failure(&err) => {
+                    self.reconnect().map_err(flatten_error)?;
+                    continue;
                 }
+                response => response.map_err(flatten_error)?,
             };
+            let response = response
+                .ok_or_else(|| timed_out(io::Error::from(io::ErrorKind::WouldBlock).into()))?;
+            if !is_notification(&response) {
+                continue;
+            }
+            if let NlPayload::Payload(msg) = response.nl_payload {
+                return Ok((&msg).try_into()?);
+            }
         }
+    }
 
-        Ok(retval.unwrap_or_default())
+    /// Parse the attributes of a reply, skipping the malformed ones in lenient mode
+    fn parse<T>(&mut self, msg: &Nl80211Msg) -> Result<T, DeError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        parse_attrs(msg, self.options.is_lenient(), &mut self.warnings)
     }
 
-    pub fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(
-            Nl80211Cmd::CmdGetScan,
-            NL_80211_GENL_VERSION,
-            {
-                let mut attrs = GenlBuffer::new();
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
-                attrs
-            },
-        );
+    /// Send a command and wait for the kernel to acknowledge it
+    fn execute(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), NlError> {
+        self.request(cmd, attrs).map(drop)
+    }
+
+    /// Send a command and collect the replies until the kernel acknowledges it
+    fn request(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        self.transact(cmd, attrs, &[NlmF::Request, NlmF::Ack])
+    }
+
+    /// Send a dump request and collect the replies
+    fn dump(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        self.transact(cmd, attrs, &[NlmF::Request, NlmF::Dump])
+    }
+
+    /// Send a command and collect the replies, reconnecting and retrying once if the socket
+    /// fails and auto-reconnect is enabled
+    fn transact(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+        flags: &[NlmF],
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        if !self.options.auto_reconnect {
+            return self.try_transact(cmd, attrs, flags).map_err(flatten_error);
+        }
+
+        let retry_attrs = is_query(cmd, flags).then(|| copy_attrs(&attrs));
+        match self.try_transact(cmd, attrs, flags) {
+            Err(err) if is_socket_failure(&err) => {
+                self.reconnect().map_err(flatten_error)?;
+                match retry_attrs {
+                    Some(attrs) => self.try_transact(cmd, attrs, flags).map_err(flatten_error),
+                    None => Err(flatten_error(err)),
+                }
+            }
+            res => res.map_err(flatten_error),
+        }
+    }
+
+    fn try_transact(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+        flags: &[NlmF],
+    ) -> Result<Vec<Nl80211Msg>, NlError<Nlmsg, Nl80211Msg>> {
+        let seq = next_seq(&mut self.seq);
+        let pid = self.sock.pid()?;
+        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(cmd, self.options.version(), attrs);
 
         let nlhdr = {
             let len = None;
             let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
+            let flags = NlmFFlags::new(flags);
+            let seq = Some(seq);
             let pid = None;
             let payload = NlPayload::Payload(msghdr);
             Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
@@ -180,30 +944,210 @@ impl Socket {
 
         self.sock.send(nlhdr)?;
 
-        let iter = self
-            .sock
-            .iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(false);
-
-        let mut retval = Vec::new();
+        let sock = &mut self.sock;
+        collect_replies(|| sock.recv(), seq, pid, &mut self.notifications)
+    }
+}
 
-        for response in iter {
-            let response = response.unwrap();
-            match response.nl_type {
-                Nlmsg::Noop => (),
-                Nlmsg::Error => panic!("Error"),
-                Nlmsg::Done => break,
-                _ => retval.push(
-                    response
-                        .nl_payload
-                        .get_payload()
-                        .unwrap()
-                        .get_attr_handle()
-                        .try_into()?,
-                ),
+/// Receive the replies to the request with the given sequence number until the kernel
+/// acknowledges it or ends the dump
+///
+/// Multicast notifications or registered frames may be received in between, they are added to
+/// `notifications` for [`Socket::next_event`]. Replies to other requests, e.g. the rest of a dump
+/// which timed out, are discarded.
+fn collect_replies<F>(
+    mut recv: F,
+    seq: u32,
+    pid: u32,
+    notifications: &mut VecDeque<Nl80211Msg>,
+) -> Result<Vec<Nl80211Msg>, NlError<Nlmsg, Nl80211Msg>>
+where
+    F: FnMut() -> Result<Option<Nlmsghdr<Nlmsg, Nl80211Msg>>, NlError<Nlmsg, Nl80211Msg>>,
+{
+    let mut replies = Vec::new();
+    loop {
+        let response = match recv() {
+            Ok(Some(response)) => response,
+            // The receive timeout expired before the end of the replies
+            Ok(None) => return Err(timed_out(io::Error::from(io::ErrorKind::WouldBlock).into())),
+            Err(NlError::Nlmsgerr(err)) if err.nlmsg.nl_seq != seq => continue,
+            Err(err) => return Err(err),
+        };
+        if !is_reply(&response, seq, pid) {
+            // Late replies to a previous request are dropped
+            if is_notification(&response) {
+                if let NlPayload::Payload(msg) = response.nl_payload {
+                    notifications.push_back(msg);
+                }
             }
+            continue;
         }
+        match response.nl_payload {
+            NlPayload::Payload(msg) => replies.push(msg),
+            NlPayload::Ack(_) => break,
+            _ if response.nl_type == Nlmsg::Done => break,
+            _ => (),
+        }
+    }
 
-        Ok(retval)
+    Ok(replies)
+}
+
+#[cfg(test)]
+impl Socket {
+    /// A socket on the generic netlink controller, for testing without nl80211
+    pub(crate) fn controller() -> Self {
+        Socket {
+            sock: NlSocketHandle::connect(NlFamily::Generic, None, &[]).unwrap(),
+            family_id: libc::GENL_ID_CTRL as u16,
+            options: SocketBuilder::new(),
+            groups: Vec::new(),
+            seq: 0,
+            warnings: Vec::new(),
+            notifications: VecDeque::new(),
+            recv_timeout: None,
+            #[cfg(feature = "rtnetlink")]
+            link: None,
+        }
+    }
+}
+
+/// Whether a message is a reply to the request with the given sequence number sent from the
+/// given port, rather than a notification
+pub(crate) fn is_reply<T, P>(msg: &Nlmsghdr<T, P>, seq: u32, pid: u32) -> bool {
+    msg.nl_seq == seq && msg.nl_pid == pid
+}
+
+/// Whether a message is a notification from the kernel rather than a reply to a request
+pub(crate) fn is_notification<T, P>(msg: &Nlmsghdr<T, P>) -> bool {
+    msg.nl_seq == 0
+}
+
+/// Sequence number of the next request, skipping 0 which is used by notifications
+pub(crate) fn next_seq(seq: &mut u32) -> u32 {
+    *seq = seq.wrapping_add(1).max(1);
+    *seq
+}
+
+/// Whether an error comes from the netlink socket itself rather than from the kernel's answer
+fn is_socket_failure<T, P>(err: &NlError<T, P>) -> bool {
+    match err {
+        NlError::Wrapped(WrappedError::IOError(err))
+        | NlError::Ser(SerError::Wrapped(WrappedError::IOError(err))) => {
+            // A receive timeout expired, the socket is fine
+            !matches!(
+                err.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Whether a request only reads state, so it can safely be sent again
+fn is_query(cmd: Nl80211Cmd, flags: &[NlmF]) -> bool {
+    flags.contains(&NlmF::Dump)
+        || matches!(
+            cmd,
+            Nl80211Cmd::CmdGetWiphy
+                | Nl80211Cmd::CmdGetInterface
+                | Nl80211Cmd::CmdGetStation
+                | Nl80211Cmd::CmdGetScan
+                | Nl80211Cmd::CmdGetSurvey
+                | Nl80211Cmd::CmdGetReg
+                | Nl80211Cmd::CmdGetPowerSave
+                | Nl80211Cmd::CmdGetWowlan
+                | Nl80211Cmd::CmdGetProtocolFeatures
+        )
+}
+
+/// Report the receive timeout of the socket as [`io::ErrorKind::TimedOut`]
+pub(crate) fn timed_out<T, P>(err: NlError<T, P>) -> NlError<T, P> {
+    match err {
+        NlError::Wrapped(WrappedError::IOError(err)) if err.kind() == io::ErrorKind::WouldBlock => {
+            io::Error::new(io::ErrorKind::TimedOut, "The operation timed out").into()
+        }
+        err => err,
+    }
+}
+
+/// Report a missing nl80211 family as [`Nl80211NotAvailable`]
+fn not_available<T, P>(err: NlError<T, P>) -> NlError<T, P> {
+    match err {
+        // The controller answers ENOENT for unknown families
+        NlError::Nlmsgerr(ref msg) if msg.error == -libc::ENOENT => {
+            io::Error::new(io::ErrorKind::NotFound, Nl80211NotAvailable).into()
+        }
+        // Returned by neli when the answer has no family ID
+        NlError::Msg(ref msg) if *msg == family_not_found_msg() => {
+            io::Error::new(io::ErrorKind::NotFound, Nl80211NotAvailable).into()
+        }
+        err => err,
+    }
+}
+
+/// Message of the error returned by [`NlSocketHandle::resolve_genl_family`] when the answer of
+/// the controller has no family ID
+fn family_not_found_msg() -> String {
+    format!(
+        "Generic netlink family {} was not found",
+        NL_80211_GENL_NAME
+    )
+}
+
+/// Whether an error reports that the kernel dropped messages because the socket's receive
+/// buffer was full
+pub(crate) fn is_events_lost(err: &WrappedError) -> bool {
+    match err {
+        WrappedError::IOError(err) => err.raw_os_error() == Some(libc::ENOBUFS),
+        _ => false,
+    }
+}
+
+fn set_recv_buffer_size(sock: &NlSocketHandle, size: usize) -> io::Result<()> {
+    let size = libc::c_int::try_from(size).unwrap_or(libc::c_int::MAX);
+    let set = |option| unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::SOL_SOCKET,
+            option,
+            &size as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    // SO_RCVBUFFORCE may exceed rmem_max but needs CAP_NET_ADMIN
+    if set(libc::SO_RCVBUFFORCE) == 0 || set(libc::SO_RCVBUF) == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Copy a list of attributes, so a request can be sent again
+fn copy_attrs(attrs: &GenlBuffer<Nl80211Attr, Buffer>) -> GenlBuffer<Nl80211Attr, Buffer> {
+    attrs.iter().map(copy_attr).collect()
+}
+
+pub(crate) fn copy_attr(attr: &Nlattr<Nl80211Attr, Buffer>) -> Nlattr<Nl80211Attr, Buffer> {
+    Nlattr {
+        nla_len: attr.nla_len,
+        nla_type: attr.nla_type.clone(),
+        nla_payload: Buffer::from(attr.nla_payload.as_ref()),
+    }
+}
+
+/// Turn an error received while reading a response into a plain [`NlError`], keeping the errno
+/// reported by the kernel.
+pub(crate) fn flatten_error<T, P>(err: NlError<T, P>) -> NlError {
+    match err {
+        NlError::Nlmsgerr(err) => io::Error::from_raw_os_error(-err.error).into(),
+        NlError::Msg(msg) => NlError::Msg(msg),
+        NlError::Ser(err) => NlError::Ser(err),
+        NlError::De(err) => NlError::De(err),
+        NlError::Wrapped(err) => NlError::Wrapped(err),
+        NlError::NoAck => NlError::NoAck,
+        NlError::BadSeq => NlError::BadSeq,
+        NlError::BadPid => NlError::BadPid,
     }
 }
 
@@ -213,3 +1157,219 @@ impl From<Socket> for NlSocketHandle {
         sock.sock
     }
 }
+
+#[cfg(test)]
+mod test_socket {
+    use super::*;
+
+    #[test]
+    fn test_copy_attrs() {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfindex, 3i32).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrSsid, &b"ssid"[..]).unwrap());
+        assert_eq!(copy_attrs(&attrs), attrs);
+    }
+
+    #[test]
+    fn test_is_socket_failure() {
+        let err: NlError = io::Error::from_raw_os_error(libc::ENOBUFS).into();
+        assert!(is_socket_failure(&err));
+        let err: NlError = flatten_error(NlError::<Nlmsg, ()>::NoAck);
+        assert!(!is_socket_failure(&err));
+        let err: NlError = io::Error::from(io::ErrorKind::WouldBlock).into();
+        assert!(!is_socket_failure(&err));
+        let err: NlError = io::Error::from(io::ErrorKind::TimedOut).into();
+        assert!(!is_socket_failure(&err));
+    }
+
+    #[test]
+    fn test_is_query() {
+        let request = [NlmF::Request, NlmF::Ack];
+        assert!(is_query(
+            Nl80211Cmd::CmdGetScan,
+            &[NlmF::Request, NlmF::Dump]
+        ));
+        assert!(is_query(Nl80211Cmd::CmdGetInterface, &request));
+        assert!(!is_query(Nl80211Cmd::CmdTriggerScan, &request));
+        assert!(!is_query(Nl80211Cmd::CmdConnect, &request));
+        assert!(!is_query(Nl80211Cmd::CmdRegisterFrame, &request));
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let err: NlError = timed_out(io::Error::from(io::ErrorKind::WouldBlock).into());
+        match err {
+            NlError::Wrapped(WrappedError::IOError(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::TimedOut)
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_not_available() {
+        let err = not_available(NlError::<Nlmsg, ()>::new(family_not_found_msg()));
+        assert!(Nl80211NotAvailable::matches(&err));
+        let err = not_available(NlError::<Nlmsg, ()>::new("Failed to resolve the family"));
+        assert!(!Nl80211NotAvailable::matches(&err));
+        let err = not_available::<Nlmsg, ()>(io::Error::from_raw_os_error(libc::EPERM).into());
+        assert!(!Nl80211NotAvailable::matches(&err));
+    }
+
+    #[test]
+    fn test_is_reply() {
+        let mut seq = u32::MAX - 1;
+        assert_eq!(next_seq(&mut seq), u32::MAX);
+        assert_eq!(next_seq(&mut seq), 1);
+
+        let reply = Nlmsghdr::new(
+            None,
+            Nlmsg::Noop,
+            NlmFFlags::empty(),
+            Some(1),
+            Some(42),
+            NlPayload::<Nlmsg, ()>::Empty,
+        );
+        assert!(is_reply(&reply, 1, 42));
+        assert!(!is_reply(&reply, 2, 42));
+        let notification = Nlmsghdr::new(
+            None,
+            Nlmsg::Noop,
+            NlmFFlags::empty(),
+            Some(0),
+            Some(0),
+            NlPayload::<Nlmsg, ()>::Empty,
+        );
+        assert!(!is_reply(&notification, 1, 42));
+    }
+
+    #[test]
+    fn test_collect_replies_keeps_notifications() {
+        let msg = |cmd, seq, pid| {
+            Nlmsghdr::new(
+                None,
+                Nlmsg::Noop,
+                NlmFFlags::empty(),
+                Some(seq),
+                Some(pid),
+                NlPayload::Payload(Genlmsghdr::new(cmd, 1, GenlBuffer::new())),
+            )
+        };
+        let done = Nlmsghdr::new(
+            None,
+            Nlmsg::Done,
+            NlmFFlags::empty(),
+            Some(1),
+            Some(42),
+            NlPayload::Empty,
+        );
+        let mut responses = vec![
+            msg(Nl80211Cmd::CmdNewInterface, 1, 42),
+            msg(Nl80211Cmd::CmdFrame, 0, 0),
+            msg(Nl80211Cmd::CmdNewInterface, 1, 42),
+            done,
+        ]
+        .into_iter();
+
+        let mut notifications = VecDeque::new();
+        let replies = collect_replies(|| Ok(responses.next()), 1, 42, &mut notifications).unwrap();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].cmd, Nl80211Cmd::CmdFrame);
+    }
+
+    #[test]
+    fn test_collect_replies_drops_stale_replies() {
+        let msg = |cmd, seq, flags: &[NlmF]| {
+            Nlmsghdr::new(
+                None,
+                Nlmsg::Noop,
+                NlmFFlags::new(flags),
+                Some(seq),
+                Some(42),
+                NlPayload::Payload(Genlmsghdr::new(cmd, 1, GenlBuffer::new())),
+            )
+        };
+        // The rest of a dump which timed out, then the reply to the next request
+        let mut responses = vec![
+            msg(Nl80211Cmd::CmdNewInterface, 1, &[NlmF::Multi]),
+            msg(Nl80211Cmd::CmdNewScanResults, 1, &[NlmF::Multi]),
+            msg(Nl80211Cmd::CmdNewStation, 2, &[NlmF::Multi]),
+            Nlmsghdr::new(
+                None,
+                Nlmsg::Done,
+                NlmFFlags::empty(),
+                Some(2),
+                Some(42),
+                NlPayload::Empty,
+            ),
+        ]
+        .into_iter();
+
+        let mut notifications = VecDeque::new();
+        let replies = collect_replies(|| Ok(responses.next()), 2, 42, &mut notifications).unwrap();
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].cmd, Nl80211Cmd::CmdNewStation);
+        assert!(notifications.is_empty());
+    }
+
+    #[test]
+    fn test_collect_replies_timeout() {
+        let mut sock = NlSocketHandle::connect(NlFamily::Generic, None, &[]).unwrap();
+        sock.nonblock().unwrap();
+        // The first part of a dump, then nothing arrives on the socket anymore
+        let mut responses = (0..2).map(|_| {
+            Nlmsghdr::new(
+                None,
+                Nlmsg::Noop,
+                NlmFFlags::new(&[NlmF::Multi]),
+                Some(1),
+                Some(42),
+                NlPayload::Payload(Genlmsghdr::new(
+                    Nl80211Cmd::CmdNewScanResults,
+                    1,
+                    GenlBuffer::new(),
+                )),
+            )
+        });
+
+        let err = collect_replies(
+            || match responses.next() {
+                Some(response) => Ok(Some(response)),
+                None => sock.recv(),
+            },
+            1,
+            42,
+            &mut VecDeque::new(),
+        )
+        .unwrap_err();
+        match err {
+            NlError::Wrapped(WrappedError::IOError(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::TimedOut)
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_next_event_timeout() {
+        let mut socket = Socket::controller();
+        socket
+            .set_recv_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        match socket.next_event().unwrap_err() {
+            NlError::Wrapped(WrappedError::IOError(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::TimedOut)
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_is_events_lost() {
+        let err = io::Error::from_raw_os_error(libc::ENOBUFS).into();
+        assert!(is_events_lost(&err));
+        let err = io::Error::from_raw_os_error(libc::EBADF).into();
+        assert!(!is_events_lost(&err));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::attr::{Attrs, Nl80211Attr, Nl80211RateInfo, Nl80211StaInfo};
+use crate::attr::{Attrs, Nl80211Attr, Nl80211RateInfo, Nl80211StaInfo, PayloadExt};
+use crate::rate::RateInfo;
 
-use neli::attr::Attribute;
 use neli::err::DeError;
 
 /// A struct representing a remote station (Access Point)
@@ -8,6 +8,8 @@ use neli::err::DeError;
 pub struct Station {
     /// Signal strength average (dBm)
     pub average_signal: Option<i8>,
+    /// Signal strength average of the beacons received from the station (dBm)
+    pub beacon_average_signal: Option<i8>,
     /// Count of times beacon loss was detected
     pub beacon_loss: Option<u32>,
     /// Station bssid (u8)
@@ -16,68 +18,102 @@ pub struct Station {
     pub connected_time: Option<u32>,
     /// Reception bitrate
     pub rx_bitrate: Option<u32>,
+    /// Total received bytes (MPDU length) from this station
+    pub rx_bytes: Option<u64>,
     /// Total received packets (MSDUs and MMPDUs) from this station
     pub rx_packets: Option<u32>,
+    /// Reception rate details
+    pub rx_rate: Option<RateInfo>,
     /// Signal strength of last received PPDU (dBm)
     pub signal: Option<i8>,
     /// Transmission bitrate
     pub tx_bitrate: Option<u32>,
+    /// Total transmitted bytes (MPDU length) to this station
+    pub tx_bytes: Option<u64>,
     /// Total failed packets (MPDUs) to this station
     pub tx_failed: Option<u32>,
     /// Total transmitted packets (MSDUs and MMPDUs) to this station
     pub tx_packets: Option<u32>,
+    /// Transmission rate details
+    pub tx_rate: Option<RateInfo>,
     /// Total retries (MPDUs) to this station
     pub tx_retries: Option<u32>,
 }
 
+impl Station {
+    /// Link speed in MBit/s, as shown by network managers: the transmission bitrate, or the
+    /// reception bitrate when the driver doesn't report it
+    pub fn link_speed(&self) -> Option<f64> {
+        self.tx_rate
+            .as_ref()
+            .and_then(RateInfo::mbps)
+            .or_else(|| self.rx_rate.as_ref().and_then(RateInfo::mbps))
+    }
+}
+
 impl TryFrom<Attrs<'_, Nl80211Attr>> for Station {
     type Error = DeError;
 
     fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
         let mut res = Self::default();
         if let Some(bssid) = attrs.get_attribute(Nl80211Attr::AttrMac) {
-            res.bssid = Some(Vec::from(bssid.nla_payload.as_ref()));
+            res.bssid = Some(bssid.get_mac()?);
         }
 
         if let Some(info) = attrs.get_attribute(Nl80211Attr::AttrStaInfo) {
-            let attrs = info.get_attr_handle::<Nl80211StaInfo>().unwrap();
+            let attrs = info.get_attr_handle::<Nl80211StaInfo>()?;
             for attr in attrs.iter() {
                 match attr.nla_type.nla_type {
-                    Nl80211StaInfo::StaInfoSignal => res.signal = Some(attr.get_payload_as()?),
+                    Nl80211StaInfo::StaInfoSignal => res.signal = Some(attr.get_payload_exact()?),
                     Nl80211StaInfo::StaInfoSignalAvg => {
-                        res.average_signal = Some(attr.get_payload_as()?)
+                        res.average_signal = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211StaInfo::StaInfoBeaconSignalAvg => {
+                        res.beacon_average_signal = Some(attr.get_payload_exact()?)
                     }
                     Nl80211StaInfo::StaInfoBeaconLoss => {
-                        res.beacon_loss = Some(attr.get_payload_as()?)
+                        res.beacon_loss = Some(attr.get_payload_exact()?)
                     }
                     Nl80211StaInfo::StaInfoConnectedTime => {
-                        res.connected_time = Some(attr.get_payload_as()?)
+                        res.connected_time = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211StaInfo::StaInfoRxBytes => {
+                        let bytes: u32 = attr.get_payload_exact()?;
+                        res.rx_bytes = res.rx_bytes.or(Some(bytes.into()))
+                    }
+                    Nl80211StaInfo::StaInfoTxBytes => {
+                        let bytes: u32 = attr.get_payload_exact()?;
+                        res.tx_bytes = res.tx_bytes.or(Some(bytes.into()))
+                    }
+                    Nl80211StaInfo::StaInfoRxBytes64 => {
+                        res.rx_bytes = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211StaInfo::StaInfoTxBytes64 => {
+                        res.tx_bytes = Some(attr.get_payload_exact()?)
                     }
                     Nl80211StaInfo::StaInfoRxPackets => {
-                        res.rx_packets = Some(attr.get_payload_as()?)
+                        res.rx_packets = Some(attr.get_payload_exact()?)
                     }
                     Nl80211StaInfo::StaInfoTxPackets => {
-                        res.tx_packets = Some(attr.get_payload_as()?)
+                        res.tx_packets = Some(attr.get_payload_exact()?)
                     }
                     Nl80211StaInfo::StaInfoTxRetries => {
-                        res.tx_retries = Some(attr.get_payload_as()?)
+                        res.tx_retries = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211StaInfo::StaInfoTxFailed => {
+                        res.tx_failed = Some(attr.get_payload_exact()?)
                     }
-                    Nl80211StaInfo::StaInfoTxFailed => res.tx_failed = Some(attr.get_payload_as()?),
                     Nl80211StaInfo::StaInfoRxBitrate => {
-                        if let Some(rate) = attr
-                            .get_attr_handle::<Nl80211RateInfo>()?
-                            .get_attribute(Nl80211RateInfo::RateInfoBitrate32)
-                        {
-                            res.rx_bitrate = Some(rate.get_payload_as()?);
-                        }
+                        let rate: RateInfo =
+                            attr.get_attr_handle::<Nl80211RateInfo>()?.try_into()?;
+                        res.rx_bitrate = rate.bitrate;
+                        res.rx_rate = Some(rate);
                     }
                     Nl80211StaInfo::StaInfoTxBitrate => {
-                        if let Some(rate) = attr
-                            .get_attr_handle::<Nl80211RateInfo>()?
-                            .get_attribute(Nl80211RateInfo::RateInfoBitrate32)
-                        {
-                            res.tx_bitrate = Some(rate.get_payload_as()?);
-                        }
+                        let rate: RateInfo =
+                            attr.get_attr_handle::<Nl80211RateInfo>()?.try_into()?;
+                        res.tx_bitrate = rate.bitrate;
+                        res.tx_rate = Some(rate);
                     }
                     _ => (),
                 }
@@ -220,18 +256,45 @@ mod tests_station {
             .unwrap();
         let expected_station = Station {
             average_signal: Some(i8::from_le_bytes([215])),
+            beacon_average_signal: Some(i8::from_le_bytes([216])),
             beacon_loss: Some(u32::from_le_bytes([0, 0, 0, 0])),
             bssid: Some(vec![46, 46, 46, 46, 46, 46]),
             connected_time: Some(u32::from_le_bytes([17, 27, 0, 0])),
             rx_bitrate: Some(u32::from_le_bytes([134, 1, 0, 0])),
+            rx_bytes: Some(u64::from_le_bytes([43, 98, 156, 29, 0, 0, 0, 0])),
             rx_packets: Some(u32::from_le_bytes([226, 128, 7, 0])),
+            rx_rate: Some(RateInfo {
+                bitrate: Some(390),
+                mcs: Some(4),
+                ..Default::default()
+            }),
             signal: Some(i8::from_le_bytes([218])),
             tx_bitrate: Some(u32::from_le_bytes([16, 4, 0, 0])),
+            tx_bytes: Some(u64::from_le_bytes([99, 123, 109, 1, 0, 0, 0, 0])),
             tx_failed: Some(u32::from_le_bytes([47, 0, 0, 0])),
             tx_packets: Some(u32::from_le_bytes([9, 170, 2, 0])),
+            tx_rate: Some(RateInfo {
+                bitrate: Some(1040),
+                mcs: Some(13),
+                ..Default::default()
+            }),
             tx_retries: Some(u32::from_le_bytes([27, 130, 0, 0])),
         };
 
-        assert_eq!(station, expected_station)
+        assert_eq!(station, expected_station);
+        assert_eq!(station.link_speed(), Some(104.0));
+        assert_eq!(station.tx_rate.unwrap().to_string(), "104.0 MBit/s MCS 13");
+    }
+
+    #[test]
+    fn test_parser_invalid_length() {
+        let handler = vec![new_attr(AttrMac, vec![46, 46, 46, 46])];
+        let res: Result<Station, _> = AttrHandle::new(handler.into_iter().collect()).try_into();
+        assert!(res.is_err());
+
+        // StaInfoSignal with a 4 bytes payload instead of 1
+        let handler = vec![new_attr(AttrStaInfo, vec![8, 0, 7, 0, 218, 0, 0, 0])];
+        let res: Result<Station, _> = AttrHandle::new(handler.into_iter().collect()).try_into();
+        assert!(res.is_err());
     }
 }
@@ -0,0 +1,202 @@
+use crate::attr::{Attrs, Nl80211Attr, Nl80211Bandc, Nl80211SurveyInfo, PayloadExt};
+
+use neli::err::DeError;
+
+/// Channel survey of a frequency, see [`Socket::get_survey`](crate::Socket::get_survey)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Survey {
+    /// Index of the interface the survey was requested on
+    pub index: Option<i32>,
+    /// Center frequency of the channel (MHz)
+    pub frequency: Option<u32>,
+    /// Offset of the center frequency in KHz, used by S1G channels
+    pub frequency_offset: Option<u32>,
+    /// Noise level of the channel (dBm)
+    pub noise: Option<i8>,
+    /// The wiphy is currently operating on this channel
+    pub in_use: bool,
+    /// Time the radio was on the channel (ms)
+    pub time: Option<u64>,
+    /// Time the primary channel was sensed busy (ms)
+    pub time_busy: Option<u64>,
+    /// Time the extension channel was sensed busy (ms)
+    pub time_ext_busy: Option<u64>,
+    /// Time spent receiving data (ms)
+    pub time_rx: Option<u64>,
+    /// Time spent transmitting data (ms)
+    pub time_tx: Option<u64>,
+    /// Time the radio spent scanning on the channel (ms)
+    pub time_scan: Option<u64>,
+    /// Time spent receiving frames destined to the local BSS (ms)
+    pub time_bss_rx: Option<u64>,
+}
+
+impl Survey {
+    /// Band of the channel
+    pub fn band(&self) -> Option<Nl80211Bandc> {
+        self.frequency.and_then(Nl80211Bandc::from_frequency)
+    }
+
+    /// Merge another survey of the same channel into this one, keeping the counters of the
+    /// survey which spent the most time on the channel
+    fn merge(&mut self, other: Survey) {
+        let in_use = self.in_use || other.in_use;
+        if other.time > self.time {
+            let noise = other.noise.or(self.noise);
+            *self = Self { noise, ..other };
+        } else {
+            self.noise = self.noise.or(other.noise);
+        }
+        self.in_use = in_use;
+    }
+}
+
+/// Merge the survey entries of the same frequency together, sorted by frequency
+pub(crate) fn merge_surveys(surveys: Vec<Survey>) -> Vec<Survey> {
+    let mut merged: Vec<Survey> = Vec::with_capacity(surveys.len());
+    for survey in surveys {
+        let channel = (survey.frequency, survey.frequency_offset);
+        match merged
+            .iter_mut()
+            .find(|s| (s.frequency, s.frequency_offset) == channel)
+        {
+            Some(existing) => existing.merge(survey),
+            None => merged.push(survey),
+        }
+    }
+    merged.sort_by_key(|s| (s.frequency, s.frequency_offset));
+    merged
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Survey {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        if let Some(index) = attrs.get_attribute(Nl80211Attr::AttrIfindex) {
+            res.index = Some(index.get_payload_exact()?);
+        }
+        if let Some(info) = attrs.get_attribute(Nl80211Attr::AttrSurveyInfo) {
+            let attrs = info.get_attr_handle::<Nl80211SurveyInfo>()?;
+            for attr in attrs.iter() {
+                match attr.nla_type.nla_type {
+                    Nl80211SurveyInfo::SurveyInfoFrequency => {
+                        res.frequency = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoFrequencyOffset => {
+                        res.frequency_offset = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoNoise => {
+                        res.noise = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoInUse => res.in_use = true,
+                    Nl80211SurveyInfo::SurveyInfoTime => res.time = Some(attr.get_payload_exact()?),
+                    Nl80211SurveyInfo::SurveyInfoTimeBusy => {
+                        res.time_busy = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeExtBusy => {
+                        res.time_ext_busy = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeRx => {
+                        res.time_rx = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeTx => {
+                        res.time_tx = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeScan => {
+                        res.time_scan = Some(attr.get_payload_exact()?)
+                    }
+                    Nl80211SurveyInfo::SurveyInfoTimeBssRx => {
+                        res.time_bss_rx = Some(attr.get_payload_exact()?)
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_survey {
+    use super::*;
+    use neli::genl::Nlattr;
+    use neli::types::{Buffer, GenlBuffer};
+
+    #[test]
+    fn test_parse() {
+        let mut info =
+            Nlattr::new(false, false, Nl80211Attr::AttrSurveyInfo, Buffer::new()).unwrap();
+        for attr in [
+            Nlattr::new(
+                false,
+                false,
+                Nl80211SurveyInfo::SurveyInfoFrequency,
+                5180u32,
+            )
+            .unwrap(),
+            Nlattr::new(false, false, Nl80211SurveyInfo::SurveyInfoNoise, -92i8).unwrap(),
+            Nlattr::new(false, false, Nl80211SurveyInfo::SurveyInfoInUse, ()).unwrap(),
+            Nlattr::new(false, false, Nl80211SurveyInfo::SurveyInfoTime, 1200u64).unwrap(),
+            Nlattr::new(false, false, Nl80211SurveyInfo::SurveyInfoTimeBusy, 300u64).unwrap(),
+        ] {
+            info.add_nested_attribute(&attr).unwrap();
+        }
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfindex, 3i32).unwrap());
+        attrs.push(info);
+
+        let survey: Survey = attrs.get_attr_handle().try_into().unwrap();
+        assert_eq!(
+            survey,
+            Survey {
+                index: Some(3),
+                frequency: Some(5180),
+                noise: Some(-92),
+                in_use: true,
+                time: Some(1200),
+                time_busy: Some(300),
+                ..Default::default()
+            }
+        );
+        assert_eq!(survey.band(), Some(Nl80211Bandc::Band5ghz));
+    }
+
+    #[test]
+    fn test_merge() {
+        let surveys = vec![
+            Survey {
+                frequency: Some(2437),
+                noise: Some(-95),
+                time: Some(100),
+                ..Default::default()
+            },
+            Survey {
+                frequency: Some(2412),
+                ..Default::default()
+            },
+            Survey {
+                frequency: Some(2437),
+                in_use: true,
+                time: Some(400),
+                time_busy: Some(40),
+                ..Default::default()
+            },
+        ];
+
+        let merged = merge_surveys(surveys);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].frequency, Some(2412));
+        assert_eq!(
+            merged[1],
+            Survey {
+                frequency: Some(2437),
+                noise: Some(-95),
+                in_use: true,
+                time: Some(400),
+                time_busy: Some(40),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::attr::{Nl80211Ac, Nl80211Attr, Nl80211TxqAttr};
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// EDCA parameters of a transmit queue, see [`Socket::set_txq_params`](crate::Socket::set_txq_params)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxqParams {
+    /// Access category of the queue
+    pub ac: Nl80211Ac,
+    /// Maximum burst time in units of 32 µs, 0 disables bursting
+    pub txop: u16,
+    /// Minimum contention window (slots), one less than a power of two
+    pub cwmin: u16,
+    /// Maximum contention window (slots), one less than a power of two
+    pub cwmax: u16,
+    /// Arbitration inter-frame space number (slots)
+    pub aifs: u8,
+}
+
+impl TxqParams {
+    fn attr(&self, index: u16) -> Result<Nlattr<u16, Buffer>, SerError> {
+        let mut attr = Nlattr::new(false, false, index, Buffer::new())?;
+        attr.add_nested_attribute(&Nlattr::new(
+            false,
+            false,
+            Nl80211TxqAttr::TxqAttrAc,
+            u16::from(self.ac) as u8,
+        )?)?;
+        attr.add_nested_attribute(&Nlattr::new(
+            false,
+            false,
+            Nl80211TxqAttr::TxqAttrTxop,
+            self.txop,
+        )?)?;
+        attr.add_nested_attribute(&Nlattr::new(
+            false,
+            false,
+            Nl80211TxqAttr::TxqAttrCwmin,
+            self.cwmin,
+        )?)?;
+        attr.add_nested_attribute(&Nlattr::new(
+            false,
+            false,
+            Nl80211TxqAttr::TxqAttrCwmax,
+            self.cwmax,
+        )?)?;
+        attr.add_nested_attribute(&Nlattr::new(
+            false,
+            false,
+            Nl80211TxqAttr::TxqAttrAifs,
+            self.aifs,
+        )?)?;
+        Ok(attr)
+    }
+}
+
+/// Limits of the intermediate transmit queues of mac80211, which are shared by all the
+/// interfaces of a wiphy, see [`Wiphy::txq_limits`](crate::Wiphy::txq_limits)
+///
+/// Lower limits reduce bufferbloat at the expense of throughput. The queues are only used by
+/// drivers with the [`ExtFeatureTxqs`](crate::Nl80211ExtFeatureIndex::ExtFeatureTxqs) extended
+/// feature. Fields left to `None` are not changed by
+/// [`Socket::set_txq_limits`](crate::Socket::set_txq_limits).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TxqLimits {
+    /// Maximum number of packets queued for the wiphy
+    pub limit: Option<u32>,
+    /// Maximum memory used by the queued packets (bytes)
+    pub memory_limit: Option<u32>,
+    /// Bytes dequeued from each flow per round of the scheduler
+    pub quantum: Option<u32>,
+}
+
+pub(crate) fn txq_limits_attrs(
+    phy: u32,
+    limits: &TxqLimits,
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, phy)?);
+    for (attr, value) in [
+        (Nl80211Attr::AttrTxqLimit, limits.limit),
+        (Nl80211Attr::AttrTxqMemoryLimit, limits.memory_limit),
+        (Nl80211Attr::AttrTxqQuantum, limits.quantum),
+    ] {
+        if let Some(value) = value {
+            attrs.push(Nlattr::new(false, false, attr, value)?);
+        }
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn txq_params_attrs(
+    interface_index: i32,
+    params: &[TxqParams],
+) -> Result<GenlBuffer<Nl80211Attr, Buffer>, SerError> {
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(
+        false,
+        false,
+        Nl80211Attr::AttrIfindex,
+        interface_index,
+    )?);
+    let mut queues = Nlattr::new(false, false, Nl80211Attr::AttrWiphyTxqParams, Buffer::new())?;
+    for (index, params) in (1..).zip(params) {
+        queues.add_nested_attribute(&params.attr(index)?)?;
+    }
+    attrs.push(queues);
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod test_txq {
+    use super::*;
+
+    #[test]
+    fn test_attrs() {
+        let params = [
+            TxqParams {
+                ac: Nl80211Ac::AcVo,
+                txop: 47,
+                cwmin: 3,
+                cwmax: 7,
+                aifs: 2,
+            },
+            TxqParams {
+                ac: Nl80211Ac::AcBk,
+                txop: 0,
+                cwmin: 15,
+                cwmax: 1023,
+                aifs: 7,
+            },
+        ];
+
+        let attrs = txq_params_attrs(3, &params).unwrap();
+        let attrs = attrs.get_attr_handle();
+        let queues = attrs
+            .get_attribute(Nl80211Attr::AttrWiphyTxqParams)
+            .unwrap()
+            .get_attr_handle::<u16>()
+            .unwrap();
+        assert_eq!(queues.iter().count(), 2);
+
+        let bk = queues
+            .get_attribute(2)
+            .unwrap()
+            .get_attr_handle::<Nl80211TxqAttr>()
+            .unwrap();
+        assert_eq!(
+            bk.get_attr_payload_as::<u8>(Nl80211TxqAttr::TxqAttrAc)
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            bk.get_attr_payload_as::<u16>(Nl80211TxqAttr::TxqAttrCwmax)
+                .unwrap(),
+            1023
+        );
+        assert_eq!(
+            bk.get_attr_payload_as::<u8>(Nl80211TxqAttr::TxqAttrAifs)
+                .unwrap(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_limits_attrs() {
+        let limits = TxqLimits {
+            limit: Some(1024),
+            quantum: Some(300),
+            ..Default::default()
+        };
+        let attrs = txq_limits_attrs(1, &limits).unwrap();
+        assert_eq!(attrs.iter().count(), 3);
+        let attrs = attrs.get_attr_handle();
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrTxqLimit)
+                .unwrap(),
+            1024
+        );
+        assert_eq!(
+            attrs
+                .get_attr_payload_as::<u32>(Nl80211Attr::AttrTxqQuantum)
+                .unwrap(),
+            300
+        );
+        assert!(attrs
+            .get_attribute(Nl80211Attr::AttrTxqMemoryLimit)
+            .is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::event::Event;
+use crate::interface::Interface;
+use crate::socket::{flatten_error, timed_out};
+use crate::Socket;
+
+use neli::err::NlError;
+
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Interface to wait for with [`WifiClient::wait_for_interface`](crate::WifiClient::wait_for_interface),
+/// unset fields match any interface
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InterfaceMatch {
+    /// Name of the interface, e.g. "wlan0"
+    pub name: Option<String>,
+    /// Index of the wiphy of the interface
+    pub phy: Option<u32>,
+}
+
+impl InterfaceMatch {
+    /// Match the interface with this name
+    pub fn name(name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Match any interface of this wiphy
+    pub fn phy(phy: u32) -> Self {
+        Self {
+            phy: Some(phy),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the interface matches
+    pub fn matches(&self, interface: &Interface) -> bool {
+        let name_matches = self.name.as_ref().is_none_or(|name| {
+            let interface_name = interface.name.as_deref().unwrap_or_default();
+            interface_name.strip_suffix(b"\0").unwrap_or(interface_name) == name.as_bytes()
+        });
+        let phy_matches = self.phy.is_none_or(|phy| interface.phy == Some(phy));
+        name_matches && phy_matches
+    }
+}
+
+/// Wait until an interface matching `matcher` exists, using `commands` to list the existing
+/// interfaces and a temporary socket to receive the "config" notifications
+pub(crate) fn wait_for_interface(
+    commands: &mut Socket,
+    matcher: &InterfaceMatch,
+    timeout: Option<Duration>,
+) -> Result<Interface, NlError> {
+    // Subscribe before listing, so an interface created in between isn't missed
+    let mut events = commands
+        .options()
+        .clone()
+        .connect()
+        .map_err(flatten_error)?;
+    events.subscribe("config").map_err(flatten_error)?;
+    if let Some(interface) = find_existing(commands, matcher)? {
+        return Ok(interface);
+    }
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    wait_for_new_interface(commands, &mut events, matcher, deadline)
+}
+
+fn find_existing(
+    commands: &mut Socket,
+    matcher: &InterfaceMatch,
+) -> Result<Option<Interface>, NlError> {
+    let interfaces = commands.get_interfaces_info()?;
+    Ok(interfaces.into_iter().find(|i| matcher.matches(i)))
+}
+
+/// Wait for the notification of a new interface matching `matcher`, failing with
+/// [`io::ErrorKind::TimedOut`] once `deadline` is passed
+fn wait_for_new_interface(
+    commands: &mut Socket,
+    events: &mut Socket,
+    matcher: &InterfaceMatch,
+    deadline: Option<Instant>,
+) -> Result<Interface, NlError> {
+    loop {
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(timed_out(io::Error::from(io::ErrorKind::WouldBlock).into()));
+            }
+            events.set_recv_timeout(Some(remaining))?;
+        }
+        // Fails with `TimedOut` when nothing was received before the receive timeout
+        match events.next_event()? {
+            Event::InterfaceAdded(interface) if matcher.matches(&interface) => {
+                return Ok(interface)
+            }
+            Event::EventsLost => {
+                if let Some(interface) = find_existing(commands, matcher)? {
+                    return Ok(interface);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_wait {
+    use super::*;
+    use neli::err::WrappedError;
+
+    #[test]
+    fn test_matches() {
+        let interface = Interface {
+            name: Some(b"wlan1\0".to_vec()),
+            phy: Some(2),
+            ..Default::default()
+        };
+        assert!(InterfaceMatch::name("wlan1").matches(&interface));
+        assert!(!InterfaceMatch::name("wlan").matches(&interface));
+        assert!(InterfaceMatch::phy(2).matches(&interface));
+        assert!(!InterfaceMatch {
+            name: Some("wlan1".to_string()),
+            phy: Some(0),
+        }
+        .matches(&interface));
+        assert!(InterfaceMatch::default().matches(&Interface::default()));
+    }
+
+    #[test]
+    fn test_wait_timeout() {
+        let mut commands = Socket::controller();
+        let mut events = Socket::controller();
+        let matcher = InterfaceMatch::name("wlan0");
+        for timeout in [Duration::ZERO, Duration::from_millis(20)] {
+            let deadline = Some(Instant::now() + timeout);
+            match wait_for_new_interface(&mut commands, &mut events, &matcher, deadline) {
+                Err(NlError::Wrapped(WrappedError::IOError(err))) => {
+                    assert_eq!(err.kind(), io::ErrorKind::TimedOut)
+                }
+                res => panic!("unexpected result {:?}", res),
+            }
+        }
+    }
+}
@@ -0,0 +1,540 @@
+use crate::attr::{
+    exact_payload, Attrs, NestedAttrs, Nl80211Attr, Nl80211BandAttr, Nl80211Bandc, Nl80211DfsState,
+    Nl80211ExtFeatureIndex, Nl80211FrequencyAttr, Nl80211Iftype, Nl80211WowlanTriggers, PayloadExt,
+};
+use crate::power::Mbm;
+use crate::txq::TxqLimits;
+
+use neli::err::DeError;
+
+/// Monitor interfaces can be created with
+/// [`MntrFlagActive`](crate::Nl80211MntrFlags::MntrFlagActive), to acknowledge frames
+pub const FEATURE_ACTIVE_MONITOR: u32 = 1 << 17;
+/// The driver includes the DS Parameter Set element in probe requests, see [`Wiphy::has_feature`]
+pub const FEATURE_DS_PARAM_SET_IE_IN_PROBES: u32 = 1 << 19;
+/// The driver includes the WFA TPC Report element in probe requests
+pub const FEATURE_WFA_TPC_IE_IN_PROBES: u32 = 1 << 20;
+/// The driver supports quiet periods requested by the AP
+pub const FEATURE_QUIET: u32 = 1 << 21;
+/// The driver fills in the transmit power of TPC Report elements in action frames
+pub const FEATURE_TX_POWER_INSERTION: u32 = 1 << 22;
+
+/// A struct representing a wiphy (wireless hardware)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Wiphy {
+    /// Index of the wiphy, cf. `/sys/class/ieee80211/<phyname>/index`
+    pub index: Option<u32>,
+    /// Name of the wiphy, e.g. "phy0", cf. `/sys/class/ieee80211/<phyname>`
+    pub name: Option<String>,
+    /// Maximum number of SSIDs which can be probed in a single scan
+    pub max_scan_ssids: Option<u8>,
+    /// Interface types which can be created on the wiphy
+    pub supported_iftypes: Vec<Nl80211Iftype>,
+    /// Retry limit for frames shorter than the RTS threshold
+    pub retry_short: Option<u8>,
+    /// Retry limit for frames longer than the RTS threshold
+    pub retry_long: Option<u8>,
+    /// Fragmentation threshold (bytes), `u32::MAX` when disabled
+    pub frag_threshold: Option<u32>,
+    /// RTS threshold (bytes), `u32::MAX` when disabled
+    pub rts_threshold: Option<u32>,
+    /// Coverage class, which extends the slot time and ACK timeout for long links, see
+    /// [`coverage_class_for_distance`]
+    pub coverage_class: Option<u8>,
+    /// Bitmask of driver features, e.g. [`FEATURE_QUIET`]
+    pub feature_flags: Option<u32>,
+    /// Bitmap of extended features, indexed by [`Nl80211ExtFeatureIndex`]
+    pub ext_features: Vec<u8>,
+    /// Wake on WLAN triggers supported by the hardware
+    pub wowlan_triggers: Vec<Nl80211WowlanTriggers>,
+    /// Limits of the packet patterns, if [`WowlanTrigPktPattern`](Nl80211WowlanTriggers::WowlanTrigPktPattern)
+    /// is supported
+    pub wowlan_pattern_support: Option<WowlanPatternSupport>,
+    /// Channels of all the bands supported by the wiphy, with their regulatory state
+    pub channels: Vec<Channel>,
+    /// Limits of the transmit queues, for drivers using mac80211's queues
+    pub txq_limits: TxqLimits,
+}
+
+/// A channel supported by a [`Wiphy`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Channel {
+    /// Band of the channel
+    pub band: Option<Nl80211Bandc>,
+    /// Center frequency of the channel (MHz)
+    pub frequency: u32,
+    /// The channel is disabled by the regulatory domain
+    pub disabled: bool,
+    /// Initiating radiation (beaconing, probing) isn't allowed on the channel
+    pub no_ir: bool,
+    /// Radar detection is required on the channel
+    pub radar: bool,
+    /// Maximum transmission power
+    pub max_tx_power: Option<Mbm>,
+    /// DFS state of a radar channel
+    pub dfs_state: Option<Nl80211DfsState>,
+    /// Time spent in the current DFS state (ms)
+    pub dfs_time: Option<u32>,
+    /// Duration of the Channel Availability Check (ms)
+    pub cac_time: Option<u32>,
+}
+
+impl Channel {
+    /// Whether beaconing on the channel requires a Channel Availability Check first, i.e. it
+    /// is a radar channel on which no CAC has completed yet
+    pub fn needs_cac(&self) -> bool {
+        self.radar && self.dfs_state != Some(Nl80211DfsState::DfsAvailable)
+    }
+
+    /// Whether radar was detected on the channel, which can't be used until the non-occupancy
+    /// period has elapsed
+    pub fn radar_detected(&self) -> bool {
+        self.dfs_state == Some(Nl80211DfsState::DfsUnavailable)
+    }
+
+    fn parse(band: Option<Nl80211Bandc>, payload: &[u8]) -> Result<Self, DeError> {
+        let mut res = Self {
+            band,
+            ..Default::default()
+        };
+        for attr in NestedAttrs::new(payload) {
+            let (attr_type, payload) = attr?;
+            match Nl80211FrequencyAttr::from(attr_type) {
+                Nl80211FrequencyAttr::FrequencyAttrFreq => {
+                    res.frequency = u32::from_ne_bytes(exact_payload(payload)?)
+                }
+                Nl80211FrequencyAttr::FrequencyAttrDisabled => res.disabled = true,
+                Nl80211FrequencyAttr::FrequencyAttrNoIr => res.no_ir = true,
+                Nl80211FrequencyAttr::FrequencyAttrRadar => res.radar = true,
+                Nl80211FrequencyAttr::FrequencyAttrMaxTxPower => {
+                    let mbm = u32::from_ne_bytes(exact_payload(payload)?);
+                    res.max_tx_power = Some(Mbm(i32::try_from(mbm).unwrap_or(i32::MAX)))
+                }
+                Nl80211FrequencyAttr::FrequencyAttrDfsState => {
+                    let state = u32::from_ne_bytes(exact_payload(payload)?);
+                    res.dfs_state = u16::try_from(state).ok().map(Nl80211DfsState::from)
+                }
+                Nl80211FrequencyAttr::FrequencyAttrDfsTime => {
+                    res.dfs_time = Some(u32::from_ne_bytes(exact_payload(payload)?))
+                }
+                Nl80211FrequencyAttr::FrequencyAttrDfsCacTime => {
+                    res.cac_time = Some(u32::from_ne_bytes(exact_payload(payload)?))
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Parse the channels of the bands attribute, nesting bands, their attributes and frequencies
+fn parse_channels(payload: &[u8], channels: &mut Vec<Channel>) -> Result<(), DeError> {
+    for band in NestedAttrs::new(payload) {
+        let (band, band_attrs) = band?;
+        let band = Some(Nl80211Bandc::from(band));
+        for band_attr in NestedAttrs::new(band_attrs) {
+            let (attr_type, frequencies) = band_attr?;
+            if Nl80211BandAttr::from(attr_type) != Nl80211BandAttr::BandAttrFreqs {
+                continue;
+            }
+            for frequency in NestedAttrs::new(frequencies) {
+                let (_, frequency) = frequency?;
+                channels.push(Channel::parse(band, frequency)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Limits of the packet patterns which can wake the system
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WowlanPatternSupport {
+    /// Maximum number of patterns
+    pub max_patterns: u32,
+    /// Minimum length of a pattern (bytes)
+    pub min_pattern_len: u32,
+    /// Maximum length of a pattern (bytes)
+    pub max_pattern_len: u32,
+    /// Maximum offset of a pattern in the packet (bytes)
+    pub max_pkt_offset: u32,
+}
+
+impl WowlanPatternSupport {
+    fn parse(payload: &[u8]) -> Result<Self, DeError> {
+        let fields: [u8; 16] = exact_payload(payload)?;
+        let field =
+            |i: usize| u32::from_ne_bytes(exact_payload(&fields[i * 4..i * 4 + 4]).unwrap());
+        Ok(Self {
+            max_patterns: field(0),
+            min_pattern_len: field(1),
+            max_pattern_len: field(2),
+            max_pkt_offset: field(3),
+        })
+    }
+}
+
+impl Wiphy {
+    /// Whether the driver advertises a feature flag, such as [`FEATURE_QUIET`]
+    pub fn has_feature(&self, flag: u32) -> bool {
+        self.feature_flags.is_some_and(|flags| flags & flag == flag)
+    }
+
+    /// Whether the driver advertises an extended feature
+    pub fn has_ext_feature(&self, feature: Nl80211ExtFeatureIndex) -> bool {
+        let index = usize::from(u16::from(feature));
+        self.ext_features
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Whether radio resource management (802.11k) can be requested on connect, see
+    /// [`ConnectParams::use_rrm`](crate::ConnectParams::use_rrm)
+    pub fn supports_rrm(&self) -> bool {
+        self.has_ext_feature(Nl80211ExtFeatureIndex::ExtFeatureRrm)
+    }
+
+    /// Whether the driver can do the 4-way handshake of PSK networks itself, see
+    /// [`ConnectParams::pmk`](crate::ConnectParams::pmk)
+    pub fn supports_psk_handshake_offload(&self) -> bool {
+        self.has_ext_feature(Nl80211ExtFeatureIndex::ExtFeature4wayHandshakeStaPsk)
+    }
+
+    /// Whether the driver can do the 4-way handshake of 802.1X networks itself, see
+    /// [`ConnectParams::want_1x_handshake_offload`](crate::ConnectParams::want_1x_handshake_offload)
+    pub fn supports_1x_handshake_offload(&self) -> bool {
+        self.has_ext_feature(Nl80211ExtFeatureIndex::ExtFeature4wayHandshakeSta1x)
+    }
+
+    /// Whether the hardware can wake the system on a trigger, such as
+    /// [`WowlanTrigMagicPkt`](Nl80211WowlanTriggers::WowlanTrigMagicPkt)
+    pub fn supports_wowlan_trigger(&self, trigger: Nl80211WowlanTriggers) -> bool {
+        self.wowlan_triggers.contains(&trigger)
+    }
+
+    /// The channel with the given center frequency (MHz)
+    pub fn channel(&self, frequency: u32) -> Option<&Channel> {
+        self.channels
+            .iter()
+            .find(|channel| channel.frequency == frequency)
+    }
+
+    /// Merge the information of another message of a split wiphy dump into this one
+    fn merge(&mut self, other: Wiphy) {
+        self.name = self.name.take().or(other.name);
+        self.max_scan_ssids = self.max_scan_ssids.or(other.max_scan_ssids);
+        self.supported_iftypes.extend(other.supported_iftypes);
+        self.retry_short = self.retry_short.or(other.retry_short);
+        self.retry_long = self.retry_long.or(other.retry_long);
+        self.frag_threshold = self.frag_threshold.or(other.frag_threshold);
+        self.rts_threshold = self.rts_threshold.or(other.rts_threshold);
+        self.coverage_class = self.coverage_class.or(other.coverage_class);
+        self.feature_flags = self.feature_flags.or(other.feature_flags);
+        if self.ext_features.is_empty() {
+            self.ext_features = other.ext_features;
+        }
+        if self.wowlan_triggers.is_empty() {
+            self.wowlan_triggers = other.wowlan_triggers;
+        }
+        self.wowlan_pattern_support = self.wowlan_pattern_support.or(other.wowlan_pattern_support);
+        // Split dumps send the channels of a band over several messages
+        self.channels.extend(other.channels);
+        self.txq_limits = TxqLimits {
+            limit: self.txq_limits.limit.or(other.txq_limits.limit),
+            memory_limit: self
+                .txq_limits
+                .memory_limit
+                .or(other.txq_limits.memory_limit),
+            quantum: self.txq_limits.quantum.or(other.txq_limits.quantum),
+        };
+    }
+}
+
+/// Smallest coverage class covering a link of `distance` meters, like `iw phy set distance`
+///
+/// Each class adds 3 µs to the slot time, i.e. 450 m of range for the round trip.
+pub fn coverage_class_for_distance(distance: u32) -> u8 {
+    const METERS_PER_CLASS: u32 = 450;
+
+    u8::try_from(distance.div_ceil(METERS_PER_CLASS)).unwrap_or(u8::MAX)
+}
+
+/// Merge the messages of a split wiphy dump into one [`Wiphy`] per index
+pub(crate) fn merge_wiphys(parts: Vec<Wiphy>) -> Vec<Wiphy> {
+    let mut wiphys: Vec<Wiphy> = Vec::new();
+    for part in parts {
+        match wiphys.iter_mut().find(|wiphy| wiphy.index == part.index) {
+            Some(wiphy) => wiphy.merge(part),
+            None => wiphys.push(part),
+        }
+    }
+    wiphys
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Wiphy {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrWiphy => res.index = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrWiphyName => {
+                    let name = String::from_utf8_lossy(attr.nla_payload.as_ref());
+                    res.name = Some(name.trim_end_matches('\0').to_string())
+                }
+                Nl80211Attr::AttrMaxNumScanSsids => {
+                    res.max_scan_ssids = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrSupportedIftypes => {
+                    let iftypes = attr.get_attr_handle::<Nl80211Iftype>()?;
+                    res.supported_iftypes = iftypes
+                        .iter()
+                        .map(|iftype| iftype.nla_type.nla_type)
+                        .collect();
+                }
+                Nl80211Attr::AttrWiphyRetryShort => {
+                    res.retry_short = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrWiphyRetryLong => res.retry_long = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrWiphyFragThreshold => {
+                    res.frag_threshold = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrWiphyRtsThreshold => {
+                    res.rts_threshold = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrWiphyCoverageClass => {
+                    res.coverage_class = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrFeatureFlags => {
+                    res.feature_flags = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrExtFeatures => {
+                    res.ext_features = attr.nla_payload.as_ref().to_vec()
+                }
+                Nl80211Attr::AttrWowlanTriggersSupported => {
+                    let triggers = attr.get_attr_handle::<Nl80211WowlanTriggers>()?;
+                    for trigger in triggers.iter() {
+                        let trigger_type = trigger.nla_type.nla_type;
+                        if trigger_type == Nl80211WowlanTriggers::WowlanTrigPktPattern {
+                            res.wowlan_pattern_support =
+                                Some(WowlanPatternSupport::parse(trigger.nla_payload.as_ref())?);
+                        }
+                        res.wowlan_triggers.push(trigger_type);
+                    }
+                }
+                Nl80211Attr::AttrTxqLimit => res.txq_limits.limit = Some(attr.get_payload_exact()?),
+                Nl80211Attr::AttrTxqMemoryLimit => {
+                    res.txq_limits.memory_limit = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrTxqQuantum => {
+                    res.txq_limits.quantum = Some(attr.get_payload_exact()?)
+                }
+                Nl80211Attr::AttrWiphyBands => {
+                    parse_channels(attr.nla_payload.as_ref(), &mut res.channels)?
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_wiphy {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let parts = vec![
+            Wiphy {
+                index: Some(0),
+                max_scan_ssids: Some(4),
+                ..Default::default()
+            },
+            Wiphy {
+                index: Some(1),
+                ..Default::default()
+            },
+            Wiphy {
+                index: Some(0),
+                supported_iftypes: vec![Nl80211Iftype::IftypeStation, Nl80211Iftype::IftypeAp],
+                rts_threshold: Some(u32::MAX),
+                ..Default::default()
+            },
+        ];
+
+        let wiphys = merge_wiphys(parts);
+        assert_eq!(wiphys.len(), 2);
+        assert_eq!(
+            wiphys[0],
+            Wiphy {
+                index: Some(0),
+                max_scan_ssids: Some(4),
+                supported_iftypes: vec![Nl80211Iftype::IftypeStation, Nl80211Iftype::IftypeAp],
+                rts_threshold: Some(u32::MAX),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_features() {
+        use neli::genl::Nlattr;
+        use neli::types::GenlBuffer;
+
+        let mut attrs = GenlBuffer::new();
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphy, 0u32).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrWiphyName, "phy0").unwrap());
+        attrs.push(
+            Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrFeatureFlags,
+                FEATURE_QUIET | FEATURE_TX_POWER_INSERTION,
+            )
+            .unwrap(),
+        );
+        attrs.push(
+            Nlattr::new(
+                false,
+                false,
+                Nl80211Attr::AttrExtFeatures,
+                [0x02u8, 0x80, 0x00].as_slice(),
+            )
+            .unwrap(),
+        );
+
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrTxqLimit, 8192u32).unwrap());
+        attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrTxqQuantum, 300u32).unwrap());
+
+        let wiphy: Wiphy = attrs.get_attr_handle().try_into().unwrap();
+        assert_eq!(wiphy.name.as_deref(), Some("phy0"));
+        assert_eq!(
+            wiphy.txq_limits,
+            TxqLimits {
+                limit: Some(8192),
+                memory_limit: None,
+                quantum: Some(300),
+            }
+        );
+        assert!(wiphy.has_feature(FEATURE_QUIET));
+        assert!(!wiphy.has_feature(FEATURE_QUIET | FEATURE_WFA_TPC_IE_IN_PROBES));
+        assert!(wiphy.supports_rrm());
+        assert!(!wiphy.has_ext_feature(Nl80211ExtFeatureIndex::ExtFeatureVhtIbss));
+        assert!(!wiphy.has_ext_feature(Nl80211ExtFeatureIndex::ExtFeatureBeaconRateVht));
+        assert!(wiphy.supports_psk_handshake_offload());
+        assert!(!wiphy.supports_1x_handshake_offload());
+    }
+
+    #[test]
+    fn test_coverage_class() {
+        assert_eq!(coverage_class_for_distance(0), 0);
+        assert_eq!(coverage_class_for_distance(450), 1);
+        assert_eq!(coverage_class_for_distance(451), 2);
+        assert_eq!(coverage_class_for_distance(10_000), 23);
+        assert_eq!(coverage_class_for_distance(u32::MAX), u8::MAX);
+    }
+
+    #[test]
+    fn test_wowlan_triggers() {
+        use neli::genl::Nlattr;
+        use neli::types::{Buffer, GenlBuffer};
+
+        let mut triggers = Nlattr::new(
+            false,
+            false,
+            Nl80211Attr::AttrWowlanTriggersSupported,
+            Buffer::new(),
+        )
+        .unwrap();
+        for trigger in [
+            Nl80211WowlanTriggers::WowlanTrigMagicPkt,
+            Nl80211WowlanTriggers::WowlanTrigGtkRekeyFailure,
+        ] {
+            triggers
+                .add_nested_attribute(&Nlattr::new(false, false, trigger, ()).unwrap())
+                .unwrap();
+        }
+        let pattern = [4u32, 1, 128, 64].map(u32::to_ne_bytes).concat();
+        triggers
+            .add_nested_attribute(
+                &Nlattr::new(
+                    false,
+                    false,
+                    Nl80211WowlanTriggers::WowlanTrigPktPattern,
+                    pattern.as_slice(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let mut attrs = GenlBuffer::new();
+        attrs.push(triggers);
+
+        let wiphy: Wiphy = attrs.get_attr_handle().try_into().unwrap();
+        assert!(wiphy.supports_wowlan_trigger(Nl80211WowlanTriggers::WowlanTrigMagicPkt));
+        assert!(wiphy.supports_wowlan_trigger(Nl80211WowlanTriggers::WowlanTrigGtkRekeyFailure));
+        assert!(!wiphy.supports_wowlan_trigger(Nl80211WowlanTriggers::WowlanTrigDisconnect));
+        assert_eq!(
+            wiphy.wowlan_pattern_support,
+            Some(WowlanPatternSupport {
+                max_patterns: 4,
+                min_pattern_len: 1,
+                max_pattern_len: 128,
+                max_pkt_offset: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_channels() {
+        use neli::genl::Nlattr;
+        use neli::types::GenlBuffer;
+
+        fn nla(nla_type: u16, payload: &[u8]) -> Vec<u8> {
+            let len = u16::try_from(payload.len() + 4).unwrap();
+            let mut res = [len.to_ne_bytes(), nla_type.to_ne_bytes()].concat();
+            res.extend_from_slice(payload);
+            res.resize(res.len().next_multiple_of(4), 0);
+            res
+        }
+        let u32_nla =
+            |attr: Nl80211FrequencyAttr, value: u32| nla(attr.into(), &value.to_ne_bytes());
+
+        let channel_36 = [
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrFreq, 5180),
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrMaxTxPower, 2300),
+        ]
+        .concat();
+        let channel_52 = [
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrFreq, 5260),
+            nla(Nl80211FrequencyAttr::FrequencyAttrNoIr.into(), &[]),
+            nla(Nl80211FrequencyAttr::FrequencyAttrRadar.into(), &[]),
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrDfsState, 0),
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrDfsTime, 1000),
+            u32_nla(Nl80211FrequencyAttr::FrequencyAttrDfsCacTime, 60000),
+        ]
+        .concat();
+        let frequencies = [nla(0, &channel_36), nla(1, &channel_52)].concat();
+        let band = nla(Nl80211BandAttr::BandAttrFreqs.into(), &frequencies);
+        let bands = nla(Nl80211Bandc::Band5ghz.into(), &band);
+
+        let mut attrs = GenlBuffer::new();
+        attrs.push(
+            Nlattr::new(false, false, Nl80211Attr::AttrWiphyBands, bands.as_slice()).unwrap(),
+        );
+        let wiphy: Wiphy = attrs.get_attr_handle().try_into().unwrap();
+
+        assert_eq!(wiphy.channels.len(), 2);
+        let channel = wiphy.channel(5180).unwrap();
+        assert_eq!(channel.band, Some(Nl80211Bandc::Band5ghz));
+        assert_eq!(channel.max_tx_power, Some(Mbm(2300)));
+        assert!(!channel.needs_cac());
+        let channel = wiphy.channel(5260).unwrap();
+        assert!(channel.no_ir && channel.radar);
+        assert_eq!(channel.dfs_state, Some(Nl80211DfsState::DfsUsable));
+        assert_eq!(channel.cac_time, Some(60000));
+        assert!(channel.needs_cac());
+        assert!(!channel.radar_detected());
+        assert!(wiphy.channel(2412).is_none());
+    }
+}